@@ -6,7 +6,7 @@ pub struct Program {
 
 pub type Identifier = String;
 
-#[derive(PartialEq)]
+#[derive(Clone, PartialEq)]
 pub enum Statement {
     Let { name: Identifier, value: Expression },
     Return(Expression),
@@ -30,7 +30,8 @@ impl Statement {
     }
 }
 
-#[derive(PartialEq)]
+#[allow(clippy::enum_variant_names)]
+#[derive(Clone, PartialEq)]
 pub enum Expression {
     IntegerLiteral(i64),
     Identifier(Identifier),
@@ -57,6 +58,13 @@ pub enum Expression {
         function: Box<Expression>,
         arguments: Vec<Expression>,
     },
+    StringLiteral(String),
+    ArrayLiteral(Vec<Expression>),
+    HashLiteral(Vec<(Expression, Expression)>),
+    Index {
+        left: Box<Expression>,
+        index: Box<Expression>,
+    },
 }
 
 impl Expression {
@@ -109,65 +117,96 @@ impl Expression {
             arguments,
         }
     }
+
+    pub fn new_string(value: &str) -> Self {
+        Expression::StringLiteral(String::from(value))
+    }
+
+    pub fn new_array(elements: Vec<Expression>) -> Self {
+        Expression::ArrayLiteral(elements)
+    }
+
+    pub fn new_hash(pairs: Vec<(Expression, Expression)>) -> Self {
+        Expression::HashLiteral(pairs)
+    }
+
+    pub fn new_index(left: Expression, index: Expression) -> Self {
+        Expression::Index {
+            left: Box::new(left),
+            index: Box::new(index),
+        }
+    }
 }
 
-#[derive(PartialEq)]
+#[derive(Clone, PartialEq)]
 pub struct BlockStatement(Vec<Statement>);
 
 impl BlockStatement {
     pub fn new(statements: Vec<Statement>) -> Self {
         BlockStatement(statements)
     }
+
+    pub fn statements(&self) -> &[Statement] {
+        &self.0
+    }
 }
 
-impl fmt::Debug for Program {
+impl fmt::Display for Program {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         for statement in &self.statements {
-            write!(f, "{:?}", statement)?;
+            write!(f, "{}", statement)?;
         }
         Ok(())
     }
 }
 
-impl fmt::Debug for Statement {
+impl fmt::Debug for Program {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for Statement {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Statement::Let { name, value } => write!(f, "let {} = {:?};", name, value),
-            Statement::Return(expression) => write!(f, "return {:?};", expression),
-            Statement::Expression(expression) => write!(f, "{:?}", expression),
+            Statement::Let { name, value } => write!(f, "let {} = {};", name, value),
+            Statement::Return(expression) => write!(f, "return {};", expression),
+            Statement::Expression(expression) => write!(f, "{}", expression),
         }
     }
 }
 
-impl fmt::Debug for Expression {
+impl fmt::Debug for Statement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for Expression {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Expression::IntegerLiteral(literal) => write!(f, "{}", literal),
             Expression::Identifier(identifier) => write!(f, "{}", identifier),
-            Expression::Prefix { operator, right } => write!(f, "({}{:?})", operator, right),
+            Expression::Prefix { operator, right } => write!(f, "({}{})", operator, right),
             Expression::Infix {
                 left,
                 right,
                 operator,
-            } => write!(f, "({:?} {} {:?})", left, operator, right),
+            } => write!(f, "({} {} {})", left, operator, right),
             Expression::Boolean(value) => write!(f, "{}", value),
             Expression::IfExpression {
                 condition,
                 consequence,
                 alternative,
             } => {
-                write!(f, "if ({:?}) {:?}", condition, consequence)?;
+                write!(f, "if ({}) {}", condition, consequence)?;
                 match alternative {
-                    Some(ref alternative) => write!(f, "else {:?}", alternative),
+                    Some(ref alternative) => write!(f, "else {}", alternative),
                     None => Ok(()),
                 }
             }
             Expression::FunctionLiteral { parameters, body } => {
-                let params = parameters
-                    .iter()
-                    .map(|param| format!("{:?}", param))
-                    .collect::<Vec<String>>();
-                write!(f, "fn ({}) {:?}", params.join(", "), body)
+                write!(f, "fn ({}) {}", parameters.join(", "), body)
             }
             Expression::CallExpression {
                 function,
@@ -175,24 +214,180 @@ impl fmt::Debug for Expression {
             } => {
                 let arguments = arguments
                     .iter()
-                    .map(|arg| format!("{:?}", arg))
+                    .map(|arg| format!("{}", arg))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                write!(f, "{}({})", function, arguments)
+            }
+            Expression::StringLiteral(value) => write!(f, "\"{}\"", value),
+            Expression::ArrayLiteral(elements) => {
+                let elements = elements
+                    .iter()
+                    .map(|element| format!("{}", element))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                write!(f, "[{}]", elements)
+            }
+            Expression::HashLiteral(pairs) => {
+                let pairs = pairs
+                    .iter()
+                    .map(|(key, value)| format!("{}: {}", key, value))
                     .collect::<Vec<String>>()
                     .join(", ");
-                write!(f, "{:?}({})", function, arguments)
+                write!(f, "{{{}}}", pairs)
             }
+            Expression::Index { left, index } => write!(f, "({}[{}])", left, index),
         }
     }
 }
 
-impl fmt::Debug for BlockStatement {
+impl fmt::Debug for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for BlockStatement {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let statements = self
             .0
             .iter()
-            .map(|statement| format!("{:?}", statement))
+            .map(|statement| format!("{}", statement))
             .collect::<Vec<String>>()
             .join("; ");
 
         write!(f, "{{{}}}", statements)
     }
 }
+
+impl fmt::Debug for BlockStatement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn let_statement_echoes_its_source() {
+        let statement = Statement::new_let("x", Expression::new_integer(5));
+        assert_eq!(format!("{}", statement), "let x = 5;");
+    }
+
+    #[test]
+    fn return_statement_echoes_its_source() {
+        let statement = Statement::new_return(Expression::new_boolean(true));
+        assert_eq!(format!("{}", statement), "return true;");
+    }
+
+    #[test]
+    fn expression_statement_echoes_its_expression() {
+        let statement = Statement::new_expression(Expression::new_identifier("x"));
+        assert_eq!(format!("{}", statement), "x");
+    }
+
+    #[test]
+    fn infix_and_prefix_expressions_are_fully_parenthesized() {
+        let infix = Expression::new_infix(
+            "+",
+            Expression::new_integer(1),
+            Expression::new_integer(2),
+        );
+        assert_eq!(format!("{}", infix), "(1 + 2)");
+
+        let prefix = Expression::new_prefix("-", Expression::new_integer(5));
+        assert_eq!(format!("{}", prefix), "(-5)");
+    }
+
+    #[test]
+    fn if_expression_renders_the_consequence_and_omits_a_missing_alternative() {
+        let if_without_else = Expression::new_if(
+            Expression::new_boolean(true),
+            BlockStatement::new(vec![Statement::new_expression(Expression::new_integer(1))]),
+            None,
+        );
+        assert_eq!(format!("{}", if_without_else), "if (true) {1}");
+
+        let if_with_else = Expression::new_if(
+            Expression::new_boolean(true),
+            BlockStatement::new(vec![Statement::new_expression(Expression::new_integer(1))]),
+            Some(BlockStatement::new(vec![Statement::new_expression(
+                Expression::new_integer(2),
+            )])),
+        );
+        assert_eq!(format!("{}", if_with_else), "if (true) {1}else {2}");
+    }
+
+    #[test]
+    fn function_literal_renders_parameters_and_body() {
+        let function = Expression::new_function_literal(
+            vec![String::from("x"), String::from("y")],
+            BlockStatement::new(vec![Statement::new_expression(Expression::new_infix(
+                "+",
+                Expression::new_identifier("x"),
+                Expression::new_identifier("y"),
+            ))]),
+        );
+        assert_eq!(format!("{}", function), "fn (x, y) {(x + y)}");
+    }
+
+    #[test]
+    fn call_expression_renders_the_function_and_its_arguments() {
+        let call = Expression::new_call_expression(
+            Expression::new_identifier("add"),
+            vec![Expression::new_integer(1), Expression::new_integer(2)],
+        );
+        assert_eq!(format!("{}", call), "add(1, 2)");
+    }
+
+    #[test]
+    fn string_array_hash_and_index_literals_render_with_their_delimiters() {
+        assert_eq!(
+            format!("{}", Expression::new_string("hello")),
+            "\"hello\""
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                Expression::new_array(vec![Expression::new_integer(1), Expression::new_integer(2)])
+            ),
+            "[1, 2]"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                Expression::new_hash(vec![(
+                    Expression::new_string("one"),
+                    Expression::new_integer(1)
+                )])
+            ),
+            "{\"one\": 1}"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                Expression::new_index(Expression::new_identifier("arr"), Expression::new_integer(0))
+            ),
+            "(arr[0])"
+        );
+    }
+
+    #[test]
+    fn a_program_renders_each_statement_back_to_back() {
+        let program = Program {
+            statements: vec![
+                Statement::new_let("x", Expression::new_integer(5)),
+                Statement::new_expression(Expression::new_identifier("x")),
+            ],
+        };
+        assert_eq!(format!("{}", program), "let x = 5;x");
+    }
+
+    #[test]
+    fn debug_formatting_matches_display_formatting() {
+        let expression = Expression::new_integer(42);
+        assert_eq!(format!("{:?}", expression), format!("{}", expression));
+    }
+}
@@ -0,0 +1,175 @@
+use crate::object::Object;
+
+pub(crate) fn lookup(name: &str) -> Option<Object> {
+    let builtin = match name {
+        "len" => len,
+        "first" => first,
+        "last" => last,
+        "rest" => rest,
+        "push" => push,
+        "puts" => puts,
+        _ => return None,
+    };
+
+    Some(Object::Builtin(builtin))
+}
+
+fn wrong_arg_count(expected: &str, got: usize) -> Object {
+    Object::Error(format!(
+        "wrong number of arguments: expected {}, got {}",
+        expected, got
+    ))
+}
+
+fn len(arguments: Vec<Object>) -> Object {
+    if arguments.len() != 1 {
+        return wrong_arg_count("1", arguments.len());
+    }
+
+    match &arguments[0] {
+        Object::String(value) => Object::Integer(value.chars().count() as i64),
+        Object::Array(elements) => Object::Integer(elements.len() as i64),
+        other => Object::Error(format!("argument to `len` not supported, got {}", other.type_name())),
+    }
+}
+
+fn first(arguments: Vec<Object>) -> Object {
+    if arguments.len() != 1 {
+        return wrong_arg_count("1", arguments.len());
+    }
+
+    match &arguments[0] {
+        Object::Array(elements) => elements.first().cloned().unwrap_or(Object::Null),
+        other => Object::Error(format!("argument to `first` must be ARRAY, got {}", other.type_name())),
+    }
+}
+
+fn last(arguments: Vec<Object>) -> Object {
+    if arguments.len() != 1 {
+        return wrong_arg_count("1", arguments.len());
+    }
+
+    match &arguments[0] {
+        Object::Array(elements) => elements.last().cloned().unwrap_or(Object::Null),
+        other => Object::Error(format!("argument to `last` must be ARRAY, got {}", other.type_name())),
+    }
+}
+
+fn rest(arguments: Vec<Object>) -> Object {
+    if arguments.len() != 1 {
+        return wrong_arg_count("1", arguments.len());
+    }
+
+    match &arguments[0] {
+        Object::Array(elements) if elements.is_empty() => Object::Null,
+        Object::Array(elements) => Object::Array(elements[1..].to_vec()),
+        other => Object::Error(format!("argument to `rest` must be ARRAY, got {}", other.type_name())),
+    }
+}
+
+fn push(arguments: Vec<Object>) -> Object {
+    if arguments.len() != 2 {
+        return wrong_arg_count("2", arguments.len());
+    }
+
+    match &arguments[0] {
+        Object::Array(elements) => {
+            let mut elements = elements.clone();
+            elements.push(arguments[1].clone());
+            Object::Array(elements)
+        }
+        other => Object::Error(format!("argument to `push` must be ARRAY, got {}", other.type_name())),
+    }
+}
+
+fn puts(arguments: Vec<Object>) -> Object {
+    for argument in &arguments {
+        println!("{}", argument);
+    }
+
+    Object::Null
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup_resolves_known_names_and_rejects_unknown_ones() {
+        assert!(matches!(lookup("len"), Some(Object::Builtin(_))));
+        assert!(lookup("nope").is_none());
+    }
+
+    #[test]
+    fn len_counts_string_chars_and_array_elements() {
+        assert_eq!(
+            len(vec![Object::String(String::from("hello"))]),
+            Object::Integer(5)
+        );
+        assert_eq!(
+            len(vec![Object::Array(vec![Object::Integer(1), Object::Integer(2)])]),
+            Object::Integer(2)
+        );
+    }
+
+    #[test]
+    fn len_rejects_wrong_arity_and_unsupported_types() {
+        assert_eq!(
+            len(vec![]),
+            Object::Error(String::from("wrong number of arguments: expected 1, got 0"))
+        );
+        assert_eq!(
+            len(vec![Object::Integer(1)]),
+            Object::Error(String::from("argument to `len` not supported, got INTEGER"))
+        );
+    }
+
+    #[test]
+    fn first_and_last_return_null_for_an_empty_array() {
+        assert_eq!(first(vec![Object::Array(vec![])]), Object::Null);
+        assert_eq!(last(vec![Object::Array(vec![])]), Object::Null);
+    }
+
+    #[test]
+    fn first_and_last_return_the_respective_element() {
+        let array = Object::Array(vec![Object::Integer(1), Object::Integer(2), Object::Integer(3)]);
+        assert_eq!(first(vec![array.clone()]), Object::Integer(1));
+        assert_eq!(last(vec![array]), Object::Integer(3));
+    }
+
+    #[test]
+    fn rest_drops_the_first_element_without_mutating_the_original() {
+        let array = Object::Array(vec![Object::Integer(1), Object::Integer(2), Object::Integer(3)]);
+        assert_eq!(
+            rest(vec![array.clone()]),
+            Object::Array(vec![Object::Integer(2), Object::Integer(3)])
+        );
+        assert_eq!(
+            array,
+            Object::Array(vec![Object::Integer(1), Object::Integer(2), Object::Integer(3)])
+        );
+        assert_eq!(rest(vec![Object::Array(vec![])]), Object::Null);
+    }
+
+    #[test]
+    fn push_appends_without_mutating_the_original_array() {
+        let array = Object::Array(vec![Object::Integer(1)]);
+        assert_eq!(
+            push(vec![array.clone(), Object::Integer(2)]),
+            Object::Array(vec![Object::Integer(1), Object::Integer(2)])
+        );
+        assert_eq!(array, Object::Array(vec![Object::Integer(1)]));
+    }
+
+    #[test]
+    fn array_builtins_reject_non_array_arguments() {
+        assert_eq!(
+            first(vec![Object::Integer(1)]),
+            Object::Error(String::from("argument to `first` must be ARRAY, got INTEGER"))
+        );
+        assert_eq!(
+            push(vec![Object::Integer(1), Object::Integer(2)]),
+            Object::Error(String::from("argument to `push` must be ARRAY, got INTEGER"))
+        );
+    }
+}
@@ -0,0 +1,233 @@
+use std::fs;
+use std::process;
+use crate::compiler::Compiler;
+use crate::environment::Environment;
+use crate::evaluator;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::repl::Repl;
+use crate::vm::VM;
+
+pub fn run(args: Vec<String>) {
+    match dispatch(&args) {
+        Command::Repl => Repl::run(),
+        Command::Run(Ok(output)) => println!("{}", output),
+        Command::Run(Err(message)) => {
+            eprintln!("{}", message);
+            process::exit(1);
+        }
+    }
+}
+
+/// What `run()` should do for a given argv, with all `process::exit` calls
+/// deferred to the caller so the dispatch logic itself can be unit tested.
+enum Command {
+    Repl,
+    Run(Result<String, String>),
+}
+
+fn dispatch(args: &[String]) -> Command {
+    match args.get(1).map(String::as_str) {
+        Some("run") => {
+            let rest = &args[2..];
+            let result = match rest {
+                [flag, path] if flag == "--vm" => run_file_with_vm(path),
+                [path] => run_file(path),
+                _ => Err(usage("run [--vm] <file>")),
+            };
+            Command::Run(result)
+        }
+        Some("parse") => {
+            let result = match args.get(2) {
+                Some(path) => parse_file(path),
+                None => Err(usage("parse <file>")),
+            };
+            Command::Run(result)
+        }
+        Some("repl") | None => Command::Repl,
+        Some(other) => Command::Run(Err(usage(&format!("unknown subcommand `{}`", other)))),
+    }
+}
+
+fn usage(message: &str) -> String {
+    format!("usage: {}", message)
+}
+
+fn read_source(path: &str) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|err| format!("could not read {}: {}", path, err))
+}
+
+fn run_file(path: &str) -> Result<String, String> {
+    let source = read_source(path)?;
+    let lexer = Lexer::new(&source);
+    let mut parser = Parser::new(lexer);
+    let program = parser.parse_program();
+
+    if !parser.errors().is_empty() {
+        return Err(parser.errors().join("\n"));
+    }
+
+    let mut env = Environment::new_global();
+    let result = evaluator::eval_program(&program, &mut env);
+    if result.is_error() {
+        return Err(format!("{}", result));
+    }
+    Ok(format!("{}", result))
+}
+
+fn run_file_with_vm(path: &str) -> Result<String, String> {
+    let source = read_source(path)?;
+    let lexer = Lexer::new(&source);
+    let mut parser = Parser::new(lexer);
+    let program = parser.parse_program();
+
+    if !parser.errors().is_empty() {
+        return Err(parser.errors().join("\n"));
+    }
+
+    let bytecode = Compiler::new()
+        .compile(&program)
+        .map_err(|err| format!("compilation error: {}", err))?;
+
+    let mut vm = VM::new(bytecode);
+    vm.run().map_err(|err| format!("vm error: {}", err))?;
+
+    let result = vm.last_popped_stack_element();
+    if result.is_error() {
+        return Err(format!("{}", result));
+    }
+    Ok(format!("{}", result))
+}
+
+fn parse_file(path: &str) -> Result<String, String> {
+    let source = read_source(path)?;
+    let lexer = Lexer::new(&source);
+    let mut parser = Parser::new(lexer);
+    let program = parser.parse_program();
+
+    if !parser.errors().is_empty() {
+        return Err(parser.errors().join("\n"));
+    }
+
+    Ok(format!("{}", program))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    /// Writes `contents` to a fresh file under the system temp dir and
+    /// returns its path; the caller's file is never reused across tests,
+    /// so parallel test execution can't make them collide.
+    struct TempFile(std::path::PathBuf);
+
+    impl TempFile {
+        fn new(contents: &str) -> Self {
+            let id = COUNTER.fetch_add(1, Ordering::SeqCst);
+            let path = std::env::temp_dir().join(format!("monkey-cli-test-{}-{}", process::id(), id));
+            fs::write(&path, contents).expect("failed to write temp file");
+            TempFile(path)
+        }
+
+        fn path(&self) -> &str {
+            self.0.to_str().expect("temp path must be valid utf-8")
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            fs::remove_file(&self.0).ok();
+        }
+    }
+
+    #[test]
+    fn run_file_prints_the_final_value() {
+        let file = TempFile::new("5 + 5;");
+        assert_eq!(run_file(file.path()), Ok(String::from("10")));
+    }
+
+    #[test]
+    fn run_file_reports_parse_errors() {
+        let file = TempFile::new("let = 5;");
+        assert!(run_file(file.path()).is_err());
+    }
+
+    #[test]
+    fn run_file_reports_eval_errors() {
+        let file = TempFile::new("5 + true;");
+        assert_eq!(
+            run_file(file.path()),
+            Err(String::from("ERROR: type mismatch: INTEGER + BOOLEAN"))
+        );
+    }
+
+    #[test]
+    fn run_file_with_vm_prints_the_final_value() {
+        let file = TempFile::new("5 + 5;");
+        assert_eq!(run_file_with_vm(file.path()), Ok(String::from("10")));
+    }
+
+    #[test]
+    fn run_file_with_vm_reports_parse_errors() {
+        let file = TempFile::new("let = 5;");
+        assert!(run_file_with_vm(file.path()).is_err());
+    }
+
+    #[test]
+    fn run_file_with_vm_reports_eval_errors() {
+        let file = TempFile::new("5 + true;");
+        assert!(run_file_with_vm(file.path()).is_err());
+    }
+
+    #[test]
+    fn dispatch_runs_a_file_with_the_tree_walking_evaluator() {
+        let file = TempFile::new("5 + 5;");
+        let args = vec![String::from("monkey"), String::from("run"), String::from(file.path())];
+        match dispatch(&args) {
+            Command::Run(result) => assert_eq!(result, Ok(String::from("10"))),
+            Command::Repl => panic!("expected Command::Run"),
+        }
+    }
+
+    #[test]
+    fn dispatch_runs_a_file_with_the_vm_when_flagged() {
+        let file = TempFile::new("5 + 5;");
+        let args = vec![
+            String::from("monkey"),
+            String::from("run"),
+            String::from("--vm"),
+            String::from(file.path()),
+        ];
+        match dispatch(&args) {
+            Command::Run(result) => assert_eq!(result, Ok(String::from("10"))),
+            Command::Repl => panic!("expected Command::Run"),
+        }
+    }
+
+    #[test]
+    fn dispatch_falls_back_to_the_repl_with_no_subcommand() {
+        let args = vec![String::from("monkey")];
+        assert!(matches!(dispatch(&args), Command::Repl));
+    }
+
+    #[test]
+    fn dispatch_reports_a_usage_error_for_an_unknown_subcommand() {
+        let args = vec![String::from("monkey"), String::from("bogus")];
+        match dispatch(&args) {
+            Command::Run(Err(message)) => assert_eq!(message, "usage: unknown subcommand `bogus`"),
+            _ => panic!("expected a usage error"),
+        }
+    }
+
+    #[test]
+    fn dispatch_reports_a_usage_error_for_a_missing_run_path() {
+        let args = vec![String::from("monkey"), String::from("run")];
+        match dispatch(&args) {
+            Command::Run(Err(message)) => assert_eq!(message, "usage: run [--vm] <file>"),
+            _ => panic!("expected a usage error"),
+        }
+    }
+}
@@ -0,0 +1,149 @@
+pub type Instructions = Vec<u8>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    Constant,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    True,
+    False,
+    Null,
+    Equal,
+    NotEqual,
+    GreaterThan,
+    Minus,
+    Bang,
+    JumpNotTruthy,
+    Jump,
+    GetGlobal,
+    SetGlobal,
+    GetLocal,
+    SetLocal,
+    Array,
+    Call,
+    ReturnValue,
+    Return,
+    Pop,
+    GetFree,
+    Closure,
+}
+
+impl Opcode {
+    pub fn byte(self) -> u8 {
+        self as u8
+    }
+
+    pub fn from_byte(byte: u8) -> Option<Opcode> {
+        use Opcode::*;
+        const OPCODES: &[Opcode] = &[
+            Constant, Add, Sub, Mul, Div, True, False, Null, Equal, NotEqual,
+            GreaterThan, Minus, Bang, JumpNotTruthy, Jump, GetGlobal, SetGlobal,
+            GetLocal, SetLocal, Array, Call, ReturnValue, Return, Pop, GetFree,
+            Closure,
+        ];
+        OPCODES.get(byte as usize).copied()
+    }
+
+    /// Width in bytes of each operand this opcode takes.
+    fn operand_widths(self) -> &'static [usize] {
+        match self {
+            Opcode::Constant | Opcode::JumpNotTruthy | Opcode::Jump | Opcode::Array => &[2],
+            Opcode::GetGlobal | Opcode::SetGlobal => &[2],
+            Opcode::GetLocal | Opcode::SetLocal | Opcode::Call | Opcode::GetFree => &[1],
+            Opcode::Closure => &[2, 1],
+            _ => &[],
+        }
+    }
+}
+
+/// Encodes an opcode and its operands into their instruction bytes.
+pub fn make(op: Opcode, operands: &[usize]) -> Instructions {
+    let widths = op.operand_widths();
+    let mut instruction = Vec::with_capacity(1 + widths.iter().sum::<usize>());
+    instruction.push(op.byte());
+
+    for (operand, width) in operands.iter().zip(widths) {
+        match width {
+            2 => instruction.extend_from_slice(&(*operand as u16).to_be_bytes()),
+            1 => instruction.push(*operand as u8),
+            _ => unreachable!("unsupported operand width"),
+        }
+    }
+
+    instruction
+}
+
+pub fn read_u16(instructions: &[u8], offset: usize) -> u16 {
+    u16::from_be_bytes([instructions[offset], instructions[offset + 1]])
+}
+
+pub fn read_u8(instructions: &[u8], offset: usize) -> u8 {
+    instructions[offset]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn make_encodes_a_two_byte_operand() {
+        let instruction = make(Opcode::Constant, &[65534]);
+        assert_eq!(instruction, vec![Opcode::Constant.byte(), 255, 254]);
+    }
+
+    #[test]
+    fn make_encodes_a_one_byte_operand() {
+        let instruction = make(Opcode::GetLocal, &[255]);
+        assert_eq!(instruction, vec![Opcode::GetLocal.byte(), 255]);
+    }
+
+    #[test]
+    fn make_encodes_multiple_operands_of_different_widths() {
+        let instruction = make(Opcode::Closure, &[65534, 255]);
+        assert_eq!(instruction, vec![Opcode::Closure.byte(), 255, 254, 255]);
+    }
+
+    #[test]
+    fn read_u16_round_trips_through_make() {
+        let instruction = make(Opcode::Jump, &[4000]);
+        assert_eq!(read_u16(&instruction, 1), 4000);
+    }
+
+    #[test]
+    fn from_byte_round_trips_every_opcode_byte() {
+        const OPCODES: &[Opcode] = &[
+            Opcode::Constant,
+            Opcode::Add,
+            Opcode::Sub,
+            Opcode::Mul,
+            Opcode::Div,
+            Opcode::True,
+            Opcode::False,
+            Opcode::Null,
+            Opcode::Equal,
+            Opcode::NotEqual,
+            Opcode::GreaterThan,
+            Opcode::Minus,
+            Opcode::Bang,
+            Opcode::JumpNotTruthy,
+            Opcode::Jump,
+            Opcode::GetGlobal,
+            Opcode::SetGlobal,
+            Opcode::GetLocal,
+            Opcode::SetLocal,
+            Opcode::Array,
+            Opcode::Call,
+            Opcode::ReturnValue,
+            Opcode::Return,
+            Opcode::Pop,
+            Opcode::GetFree,
+            Opcode::Closure,
+        ];
+
+        for opcode in OPCODES {
+            assert_eq!(Opcode::from_byte(opcode.byte()), Some(*opcode));
+        }
+    }
+}
@@ -0,0 +1,443 @@
+use std::rc::Rc;
+use crate::ast::{Expression, Program, Statement};
+use crate::code::{self, Instructions, Opcode};
+use crate::object::Object;
+use crate::symbol_table::{Scope, SymbolTable};
+
+pub struct Bytecode {
+    pub instructions: Instructions,
+    pub constants: Vec<Object>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct EmittedInstruction {
+    opcode: Opcode,
+    position: usize,
+}
+
+struct CompilationScope {
+    instructions: Instructions,
+    last_instruction: Option<EmittedInstruction>,
+    previous_instruction: Option<EmittedInstruction>,
+}
+
+pub struct Compiler {
+    constants: Vec<Object>,
+    symbol_table: SymbolTable,
+    scopes: Vec<CompilationScope>,
+}
+
+impl Compiler {
+    pub fn new() -> Self {
+        Compiler {
+            constants: Vec::new(),
+            symbol_table: SymbolTable::new(),
+            scopes: vec![CompilationScope {
+                instructions: Instructions::new(),
+                last_instruction: None,
+                previous_instruction: None,
+            }],
+        }
+    }
+
+    pub fn compile(mut self, program: &Program) -> Result<Bytecode, String> {
+        for statement in &program.statements {
+            self.compile_statement(statement)?;
+        }
+
+        Ok(Bytecode {
+            instructions: self.current_instructions().clone(),
+            constants: self.constants,
+        })
+    }
+
+    fn compile_statement(&mut self, statement: &Statement) -> Result<(), String> {
+        match statement {
+            Statement::Expression(expression) => {
+                self.compile_expression(expression)?;
+                self.emit(Opcode::Pop, &[]);
+                Ok(())
+            }
+            Statement::Let { name, value } => {
+                let symbol = self.symbol_table.define(name);
+                self.compile_expression(value)?;
+                match symbol.scope {
+                    Scope::Global => self.emit(Opcode::SetGlobal, &[symbol.index]),
+                    Scope::Local => self.emit(Opcode::SetLocal, &[symbol.index]),
+                    Scope::Free => unreachable!("define() never produces a free-scoped symbol"),
+                };
+                Ok(())
+            }
+            Statement::Return(value) => {
+                self.compile_expression(value)?;
+                self.emit(Opcode::ReturnValue, &[]);
+                Ok(())
+            }
+        }
+    }
+
+    fn compile_expression(&mut self, expression: &Expression) -> Result<(), String> {
+        match expression {
+            Expression::IntegerLiteral(value) => {
+                let constant = self.add_constant(Object::Integer(*value));
+                self.emit(Opcode::Constant, &[constant]);
+                Ok(())
+            }
+            Expression::StringLiteral(value) => {
+                let constant = self.add_constant(Object::String(value.clone()));
+                self.emit(Opcode::Constant, &[constant]);
+                Ok(())
+            }
+            Expression::Boolean(true) => {
+                self.emit(Opcode::True, &[]);
+                Ok(())
+            }
+            Expression::Boolean(false) => {
+                self.emit(Opcode::False, &[]);
+                Ok(())
+            }
+            Expression::Identifier(name) => {
+                let symbol = self
+                    .symbol_table
+                    .resolve(name)
+                    .ok_or_else(|| format!("undefined variable {}", name))?;
+                match symbol.scope {
+                    Scope::Global => self.emit(Opcode::GetGlobal, &[symbol.index]),
+                    Scope::Local => self.emit(Opcode::GetLocal, &[symbol.index]),
+                    Scope::Free => self.emit(Opcode::GetFree, &[symbol.index]),
+                };
+                Ok(())
+            }
+            Expression::Prefix { operator, right } => {
+                self.compile_expression(right)?;
+                match operator.as_str() {
+                    "!" => self.emit(Opcode::Bang, &[]),
+                    "-" => self.emit(Opcode::Minus, &[]),
+                    other => return Err(format!("unknown prefix operator {}", other)),
+                };
+                Ok(())
+            }
+            Expression::Infix {
+                left,
+                right,
+                operator,
+            } => {
+                if operator == "<" {
+                    self.compile_expression(right)?;
+                    self.compile_expression(left)?;
+                    self.emit(Opcode::GreaterThan, &[]);
+                    return Ok(());
+                }
+
+                self.compile_expression(left)?;
+                self.compile_expression(right)?;
+
+                match operator.as_str() {
+                    "+" => self.emit(Opcode::Add, &[]),
+                    "-" => self.emit(Opcode::Sub, &[]),
+                    "*" => self.emit(Opcode::Mul, &[]),
+                    "/" => self.emit(Opcode::Div, &[]),
+                    ">" => self.emit(Opcode::GreaterThan, &[]),
+                    "==" => self.emit(Opcode::Equal, &[]),
+                    "!=" => self.emit(Opcode::NotEqual, &[]),
+                    other => return Err(format!("unknown infix operator {}", other)),
+                };
+                Ok(())
+            }
+            Expression::IfExpression {
+                condition,
+                consequence,
+                alternative,
+            } => {
+                self.compile_expression(condition)?;
+
+                let jump_not_truthy_pos = self.emit(Opcode::JumpNotTruthy, &[9999]);
+
+                for statement in consequence.statements() {
+                    self.compile_statement(statement)?;
+                }
+                if self.last_instruction_is(Opcode::Pop) {
+                    self.remove_last_pop();
+                }
+
+                let jump_pos = self.emit(Opcode::Jump, &[9999]);
+
+                let after_consequence_pos = self.current_instructions().len();
+                self.change_operand(jump_not_truthy_pos, after_consequence_pos);
+
+                match alternative {
+                    Some(alternative) => {
+                        for statement in alternative.statements() {
+                            self.compile_statement(statement)?;
+                        }
+                        if self.last_instruction_is(Opcode::Pop) {
+                            self.remove_last_pop();
+                        }
+                    }
+                    None => {
+                        self.emit(Opcode::Null, &[]);
+                    }
+                }
+
+                let after_alternative_pos = self.current_instructions().len();
+                self.change_operand(jump_pos, after_alternative_pos);
+
+                Ok(())
+            }
+            Expression::ArrayLiteral(elements) => {
+                for element in elements {
+                    self.compile_expression(element)?;
+                }
+                self.emit(Opcode::Array, &[elements.len()]);
+                Ok(())
+            }
+            Expression::HashLiteral(_) => Err(String::from(
+                "hash literals are not yet supported by the bytecode compiler",
+            )),
+            Expression::Index { .. } => Err(String::from(
+                "index expressions are not yet supported by the bytecode compiler",
+            )),
+            Expression::FunctionLiteral { parameters, body } => {
+                self.enter_scope();
+
+                for parameter in parameters {
+                    self.symbol_table.define(parameter);
+                }
+
+                for statement in body.statements() {
+                    self.compile_statement(statement)?;
+                }
+
+                if self.last_instruction_is(Opcode::Pop) {
+                    self.replace_last_pop_with_return();
+                }
+                if !self.last_instruction_is(Opcode::ReturnValue) {
+                    self.emit(Opcode::Null, &[]);
+                    self.emit(Opcode::ReturnValue, &[]);
+                }
+
+                let free_symbols = self.symbol_table.free_symbols.clone();
+                let num_locals = self.symbol_table.len();
+                let instructions = self.leave_scope();
+
+                // Free variables must be pushed in the *enclosing* scope, so
+                // this has to happen after leave_scope() restores it.
+                for free_symbol in &free_symbols {
+                    match free_symbol.scope {
+                        Scope::Local => self.emit(Opcode::GetLocal, &[free_symbol.index]),
+                        Scope::Free => self.emit(Opcode::GetFree, &[free_symbol.index]),
+                        Scope::Global => {
+                            unreachable!("globals are resolved directly, never captured as free")
+                        }
+                    };
+                }
+
+                let constant = self.add_constant(Object::CompiledFunction {
+                    instructions: Rc::new(instructions),
+                    num_locals,
+                    num_parameters: parameters.len(),
+                });
+                self.emit(Opcode::Closure, &[constant, free_symbols.len()]);
+                Ok(())
+            }
+            Expression::CallExpression {
+                function,
+                arguments,
+            } => {
+                self.compile_expression(function)?;
+                for argument in arguments {
+                    self.compile_expression(argument)?;
+                }
+                self.emit(Opcode::Call, &[arguments.len()]);
+                Ok(())
+            }
+        }
+    }
+
+    fn add_constant(&mut self, object: Object) -> usize {
+        self.constants.push(object);
+        self.constants.len() - 1
+    }
+
+    fn emit(&mut self, op: Opcode, operands: &[usize]) -> usize {
+        let instruction = code::make(op, operands);
+        let position = self.current_instructions().len();
+
+        self.current_scope_mut().instructions.extend(instruction);
+        self.set_last_instruction(op, position);
+
+        position
+    }
+
+    fn current_instructions(&self) -> &Instructions {
+        &self.scopes.last().expect("compiler always has a scope").instructions
+    }
+
+    fn current_scope_mut(&mut self) -> &mut CompilationScope {
+        self.scopes.last_mut().expect("compiler always has a scope")
+    }
+
+    fn set_last_instruction(&mut self, opcode: Opcode, position: usize) {
+        let scope = self.current_scope_mut();
+        scope.previous_instruction = scope.last_instruction;
+        scope.last_instruction = Some(EmittedInstruction { opcode, position });
+    }
+
+    fn last_instruction_is(&self, opcode: Opcode) -> bool {
+        self.scopes
+            .last()
+            .and_then(|scope| scope.last_instruction)
+            .map(|instruction| instruction.opcode == opcode)
+            .unwrap_or(false)
+    }
+
+    fn remove_last_pop(&mut self) {
+        let scope = self.current_scope_mut();
+        let last_position = scope
+            .last_instruction
+            .expect("remove_last_pop called without a last instruction")
+            .position;
+        scope.instructions.truncate(last_position);
+        scope.last_instruction = scope.previous_instruction;
+    }
+
+    fn replace_last_pop_with_return(&mut self) {
+        let scope = self.current_scope_mut();
+        let last_position = scope
+            .last_instruction
+            .expect("replace_last_pop_with_return called without a last instruction")
+            .position;
+        let new_instruction = code::make(Opcode::ReturnValue, &[]);
+        scope.instructions[last_position..].copy_from_slice(&new_instruction);
+        scope.last_instruction = Some(EmittedInstruction {
+            opcode: Opcode::ReturnValue,
+            position: last_position,
+        });
+    }
+
+    fn change_operand(&mut self, position: usize, operand: usize) {
+        let new_instruction = code::make(Opcode::Jump, &[operand]);
+        let scope = self.current_scope_mut();
+        scope.instructions[position + 1..position + 1 + (new_instruction.len() - 1)]
+            .copy_from_slice(&new_instruction[1..]);
+    }
+
+    fn enter_scope(&mut self) {
+        self.scopes.push(CompilationScope {
+            instructions: Instructions::new(),
+            last_instruction: None,
+            previous_instruction: None,
+        });
+
+        let outer = std::mem::replace(&mut self.symbol_table, SymbolTable::new());
+        self.symbol_table = SymbolTable::new_enclosed(outer);
+    }
+
+    fn leave_scope(&mut self) -> Instructions {
+        let scope = self.scopes.pop().expect("enter_scope/leave_scope mismatch");
+
+        let outer = std::mem::replace(&mut self.symbol_table, SymbolTable::new());
+        self.symbol_table = outer.into_outer().expect("enter_scope/leave_scope mismatch");
+
+        scope.instructions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::Lexer;
+    use crate::parser::Parser;
+
+    fn compile(source: &str) -> Bytecode {
+        let lexer = Lexer::new(source);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program();
+        assert!(parser.errors().is_empty(), "parser errors: {:?}", parser.errors());
+
+        Compiler::new().compile(&program).expect("compilation should succeed")
+    }
+
+    #[test]
+    fn integer_literals_are_added_to_the_constant_pool() {
+        let bytecode = compile("1; 2;");
+        assert_eq!(bytecode.constants, vec![Object::Integer(1), Object::Integer(2)]);
+    }
+
+    #[test]
+    fn if_without_else_back_patches_a_jump_not_truthy_and_a_null_push() {
+        let bytecode = compile("if (true) { 10 }; 3333;");
+
+        let expected = [
+            code::make(Opcode::True, &[]),
+            code::make(Opcode::JumpNotTruthy, &[10]),
+            code::make(Opcode::Constant, &[0]),
+            code::make(Opcode::Jump, &[11]),
+            code::make(Opcode::Null, &[]),
+            code::make(Opcode::Pop, &[]),
+            code::make(Opcode::Constant, &[1]),
+            code::make(Opcode::Pop, &[]),
+        ]
+        .concat();
+
+        assert_eq!(bytecode.instructions, expected);
+    }
+
+    #[test]
+    fn if_with_else_back_patches_the_jump_past_the_alternative() {
+        let bytecode = compile("if (true) { 10 } else { 20 }; 3333;");
+
+        let expected = [
+            code::make(Opcode::True, &[]),
+            code::make(Opcode::JumpNotTruthy, &[10]),
+            code::make(Opcode::Constant, &[0]),
+            code::make(Opcode::Jump, &[13]),
+            code::make(Opcode::Constant, &[1]),
+            code::make(Opcode::Pop, &[]),
+            code::make(Opcode::Constant, &[2]),
+            code::make(Opcode::Pop, &[]),
+        ]
+        .concat();
+
+        assert_eq!(bytecode.instructions, expected);
+    }
+
+    #[test]
+    fn function_literals_capture_free_variables_as_closures() {
+        // The inner function reads the outer function's parameter `a`, so it
+        // must be compiled to GetFree (not GetLocal), and the outer function
+        // must push that value via GetLocal before wrapping the inner
+        // function's constant in an OpClosure.
+        let bytecode = compile("fn(a) { fn(b) { a + b } };");
+
+        match &bytecode.constants[0] {
+            Object::CompiledFunction { instructions, .. } => {
+                let expected = [
+                    code::make(Opcode::GetFree, &[0]),
+                    code::make(Opcode::GetLocal, &[0]),
+                    code::make(Opcode::Add, &[]),
+                    code::make(Opcode::ReturnValue, &[]),
+                ]
+                .concat();
+                assert_eq!(**instructions, expected);
+            }
+            other => panic!("expected a compiled function constant, got {:?}", other),
+        }
+
+        match &bytecode.constants[1] {
+            Object::CompiledFunction { instructions, .. } => {
+                let expected = [
+                    code::make(Opcode::GetLocal, &[0]),
+                    code::make(Opcode::Closure, &[0, 1]),
+                    code::make(Opcode::ReturnValue, &[]),
+                ]
+                .concat();
+                assert_eq!(**instructions, expected);
+            }
+            other => panic!("expected a compiled function constant, got {:?}", other),
+        }
+
+        let expected_top_level = [code::make(Opcode::Closure, &[1, 0]), code::make(Opcode::Pop, &[])].concat();
+        assert_eq!(bytecode.instructions, expected_top_level);
+    }
+}
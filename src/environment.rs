@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::rc::Rc;
 use std::cell::RefCell;
+use crate::builtins;
 use crate::object::Object;
 
 #[derive(Debug, Clone)]
@@ -20,6 +21,16 @@ impl Environment {
         })))
     }
 
+    pub(crate) fn new_global() -> Self {
+        let mut env = Environment::new();
+        for name in &["len", "first", "last", "rest", "push", "puts"] {
+            if let Some(builtin) = builtins::lookup(name) {
+                env.set(String::from(*name), builtin);
+            }
+        }
+        env
+    }
+
     pub(crate) fn new_enclosed_environment(outer: Environment) -> Self {
         Environment(Rc::new(RefCell::new(InnerEnvironment {
             store: HashMap::new(),
@@ -30,7 +41,7 @@ impl Environment {
     pub(crate) fn get(&self, key: &str) -> Option<Object> {
         let env = self.0.borrow();
 
-        env.store.get(key).map(Clone::clone)
+        env.store.get(key).cloned()
             .or_else(|| env.outer.as_ref().and_then(|outer| outer.get(key)))
     }
 
@@ -0,0 +1,468 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+use crate::ast::{BlockStatement, Expression, Program, Statement};
+use crate::environment::Environment;
+use crate::object::{HashPair, Object};
+
+pub fn eval_program(program: &Program, env: &mut Environment) -> Object {
+    let mut result = Object::Null;
+
+    for statement in &program.statements {
+        result = eval_statement(statement, env);
+
+        match result {
+            Object::ReturnValue(value) => return *value,
+            Object::Error(_) => return result,
+            _ => {}
+        }
+    }
+
+    result
+}
+
+fn eval_block_statement(block: &BlockStatement, env: &mut Environment) -> Object {
+    let mut result = Object::Null;
+
+    for statement in block.statements() {
+        result = eval_statement(statement, env);
+
+        match result {
+            Object::ReturnValue(_) | Object::Error(_) => return result,
+            _ => {}
+        }
+    }
+
+    result
+}
+
+fn eval_statement(statement: &Statement, env: &mut Environment) -> Object {
+    match statement {
+        Statement::Let { name, value } => {
+            let value = eval_expression(value, env);
+            if value.is_error() {
+                return value;
+            }
+            env.set(name.clone(), value);
+            Object::Null
+        }
+        Statement::Return(expression) => {
+            let value = eval_expression(expression, env);
+            if value.is_error() {
+                return value;
+            }
+            Object::ReturnValue(Box::new(value))
+        }
+        Statement::Expression(expression) => eval_expression(expression, env),
+    }
+}
+
+fn eval_expression(expression: &Expression, env: &mut Environment) -> Object {
+    match expression {
+        Expression::IntegerLiteral(value) => Object::Integer(*value),
+        Expression::Boolean(value) => Object::Boolean(*value),
+        Expression::Identifier(name) => eval_identifier(name, env),
+        Expression::Prefix { operator, right } => {
+            let right = eval_expression(right, env);
+            if right.is_error() {
+                return right;
+            }
+            eval_prefix_expression(operator, right)
+        }
+        Expression::Infix {
+            left,
+            right,
+            operator,
+        } if operator == "|:" => eval_pipe_expression(left, right, env),
+        Expression::Infix {
+            left,
+            right,
+            operator,
+        } => {
+            let left = eval_expression(left, env);
+            if left.is_error() {
+                return left;
+            }
+            let right = eval_expression(right, env);
+            if right.is_error() {
+                return right;
+            }
+            eval_infix_expression(operator, left, right)
+        }
+        Expression::IfExpression {
+            condition,
+            consequence,
+            alternative,
+        } => eval_if_expression(condition, consequence, alternative.as_ref(), env),
+        Expression::FunctionLiteral { parameters, body } => Object::Function {
+            parameters: parameters.clone(),
+            body: Rc::new(body.clone()),
+            env: env.clone(),
+        },
+        Expression::CallExpression {
+            function,
+            arguments,
+        } => eval_call_expression(function, arguments, env),
+        Expression::StringLiteral(value) => Object::String(value.clone()),
+        Expression::ArrayLiteral(elements) => eval_array_literal(elements, env),
+        Expression::HashLiteral(pairs) => eval_hash_literal(pairs, env),
+        Expression::Index { left, index } => eval_index_expression(left, index, env),
+    }
+}
+
+fn eval_array_literal(elements: &[Expression], env: &mut Environment) -> Object {
+    let mut result = Vec::with_capacity(elements.len());
+
+    for element in elements {
+        let value = eval_expression(element, env);
+        if value.is_error() {
+            return value;
+        }
+        result.push(value);
+    }
+
+    Object::Array(result)
+}
+
+fn eval_hash_literal(pairs: &[(Expression, Expression)], env: &mut Environment) -> Object {
+    let mut result = HashMap::new();
+
+    for (key_expression, value_expression) in pairs {
+        let key = eval_expression(key_expression, env);
+        if key.is_error() {
+            return key;
+        }
+
+        let value = eval_expression(value_expression, env);
+        if value.is_error() {
+            return value;
+        }
+
+        let hash_key = match key.hash_key() {
+            Ok(hash_key) => hash_key,
+            Err(message) => return Object::Error(message),
+        };
+
+        result.insert(hash_key, HashPair { key, value });
+    }
+
+    Object::Hash(result)
+}
+
+fn eval_index_expression(left: &Expression, index: &Expression, env: &mut Environment) -> Object {
+    let left = eval_expression(left, env);
+    if left.is_error() {
+        return left;
+    }
+
+    let index = eval_expression(index, env);
+    if index.is_error() {
+        return index;
+    }
+
+    match (&left, &index) {
+        (Object::Array(elements), Object::Integer(i)) => {
+            if *i < 0 || *i as usize >= elements.len() {
+                Object::Null
+            } else {
+                elements[*i as usize].clone()
+            }
+        }
+        (Object::Hash(pairs), _) => match index.hash_key() {
+            Ok(hash_key) => pairs
+                .get(&hash_key)
+                .map(|pair| pair.value.clone())
+                .unwrap_or(Object::Null),
+            Err(message) => Object::Error(message),
+        },
+        _ => Object::Error(format!("index operator not supported: {}", left.type_name())),
+    }
+}
+
+fn eval_identifier(name: &str, env: &Environment) -> Object {
+    match env.get(name) {
+        Some(value) => value,
+        None => Object::Error(format!("identifier not found: {}", name)),
+    }
+}
+
+fn eval_prefix_expression(operator: &str, right: Object) -> Object {
+    match operator {
+        "!" => Object::Boolean(!right.is_truthy()),
+        "-" => match right {
+            Object::Integer(value) => Object::Integer(-value),
+            _ => Object::Error(format!("unknown operator: -{}", right.type_name())),
+        },
+        _ => Object::Error(format!("unknown operator: {}{}", operator, right.type_name())),
+    }
+}
+
+fn eval_infix_expression(operator: &str, left: Object, right: Object) -> Object {
+    match (&left, &right) {
+        (Object::Integer(left), Object::Integer(right)) => {
+            eval_integer_infix_expression(operator, *left, *right)
+        }
+        (Object::String(l), Object::String(r)) => match operator {
+            "+" => Object::String(format!("{}{}", l, r)),
+            "==" => Object::Boolean(l == r),
+            "!=" => Object::Boolean(l != r),
+            _ => Object::Error(format!("unknown operator: STRING {} STRING", operator)),
+        },
+        (Object::Boolean(l), Object::Boolean(r)) => match operator {
+            "==" => Object::Boolean(l == r),
+            "!=" => Object::Boolean(l != r),
+            _ => Object::Error(format!(
+                "unknown operator: {} {} {}",
+                left.type_name(),
+                operator,
+                right.type_name()
+            )),
+        },
+        _ if left.type_name() != right.type_name() => Object::Error(format!(
+            "type mismatch: {} {} {}",
+            left.type_name(),
+            operator,
+            right.type_name()
+        )),
+        _ => Object::Error(format!(
+            "unknown operator: {} {} {}",
+            left.type_name(),
+            operator,
+            right.type_name()
+        )),
+    }
+}
+
+fn eval_integer_infix_expression(operator: &str, left: i64, right: i64) -> Object {
+    match operator {
+        "+" => integer_or_error(left.checked_add(right)),
+        "-" => integer_or_error(left.checked_sub(right)),
+        "*" => integer_or_error(left.checked_mul(right)),
+        "/" => {
+            if right == 0 {
+                Object::Error(String::from("division by zero"))
+            } else {
+                integer_or_error(left.checked_div(right))
+            }
+        }
+        "<" => Object::Boolean(left < right),
+        ">" => Object::Boolean(left > right),
+        "==" => Object::Boolean(left == right),
+        "!=" => Object::Boolean(left != right),
+        _ => Object::Error(format!("unknown operator: INTEGER {} INTEGER", operator)),
+    }
+}
+
+fn integer_or_error(result: Option<i64>) -> Object {
+    match result {
+        Some(value) => Object::Integer(value),
+        None => Object::Error(String::from("integer overflow")),
+    }
+}
+
+fn eval_if_expression(
+    condition: &Expression,
+    consequence: &BlockStatement,
+    alternative: Option<&BlockStatement>,
+    env: &mut Environment,
+) -> Object {
+    let condition = eval_expression(condition, env);
+    if condition.is_error() {
+        return condition;
+    }
+
+    if condition.is_truthy() {
+        eval_block_statement(consequence, env)
+    } else if let Some(alternative) = alternative {
+        eval_block_statement(alternative, env)
+    } else {
+        Object::Null
+    }
+}
+
+fn eval_call_expression(
+    function: &Expression,
+    arguments: &[Expression],
+    env: &mut Environment,
+) -> Object {
+    let function = eval_expression(function, env);
+    if function.is_error() {
+        return function;
+    }
+
+    let mut evaluated_arguments = Vec::with_capacity(arguments.len());
+    for argument in arguments {
+        let value = eval_expression(argument, env);
+        if value.is_error() {
+            return value;
+        }
+        evaluated_arguments.push(value);
+    }
+
+    apply_function(function, evaluated_arguments)
+}
+
+/// `left |: right` calls the value that `right` evaluates to with `left` as
+/// its leading argument, e.g. `range(100) |: filter(is_prime)` evaluates
+/// `filter(is_prime)` to a callable and then invokes it as
+/// `filter(is_prime)(range(100))`. Operands are evaluated left-to-right,
+/// same as every other infix expression, so side effects run in the order
+/// the data visibly flows through the pipe.
+fn eval_pipe_expression(left: &Expression, right: &Expression, env: &mut Environment) -> Object {
+    let piped = eval_expression(left, env);
+    if piped.is_error() {
+        return piped;
+    }
+
+    let callee = eval_expression(right, env);
+    if callee.is_error() {
+        return callee;
+    }
+
+    apply_function(callee, vec![piped])
+}
+
+fn apply_function(function: Object, arguments: Vec<Object>) -> Object {
+    match function {
+        Object::Function {
+            parameters,
+            body,
+            env,
+        } => {
+            if parameters.len() != arguments.len() {
+                return Object::Error(format!(
+                    "wrong number of arguments: expected {}, got {}",
+                    parameters.len(),
+                    arguments.len()
+                ));
+            }
+
+            let mut function_env = Environment::new_enclosed_environment(env);
+            for (parameter, argument) in parameters.iter().zip(arguments) {
+                function_env.set(parameter.clone(), argument);
+            }
+
+            let evaluated = eval_block_statement(&body, &mut function_env);
+            match evaluated {
+                Object::ReturnValue(value) => *value,
+                other => other,
+            }
+        }
+        Object::Builtin(builtin) => builtin(arguments),
+        other => Object::Error(format!("not a function: {}", other.type_name())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::Lexer;
+    use crate::parser::Parser;
+
+    fn eval(source: &str) -> Object {
+        let lexer = Lexer::new(source);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program();
+        assert!(parser.errors().is_empty(), "parser errors: {:?}", parser.errors());
+
+        let mut env = Environment::new();
+        eval_program(&program, &mut env)
+    }
+
+    #[test]
+    fn integer_arithmetic() {
+        assert_eq!(eval("5 + 5 * 2 - 10 / 2;"), Object::Integer(10));
+    }
+
+    #[test]
+    fn division_by_zero_is_an_error() {
+        assert_eq!(
+            eval("10 / 0;"),
+            Object::Error(String::from("division by zero"))
+        );
+    }
+
+    #[test]
+    fn integer_overflow_is_an_error() {
+        assert_eq!(
+            eval("9223372036854775807 + 1;"),
+            Object::Error(String::from("integer overflow"))
+        );
+    }
+
+    #[test]
+    fn type_mismatch_is_an_error() {
+        assert_eq!(
+            eval("5 + true;"),
+            Object::Error(String::from("type mismatch: INTEGER + BOOLEAN"))
+        );
+    }
+
+    #[test]
+    fn unknown_identifier_is_an_error() {
+        assert_eq!(
+            eval("foobar;"),
+            Object::Error(String::from("identifier not found: foobar"))
+        );
+    }
+
+    #[test]
+    fn error_short_circuits_a_block() {
+        assert_eq!(
+            eval("if (10 > 1) { if (10 > 1) { return true + false; } return 1; }"),
+            Object::Error(String::from("unknown operator: BOOLEAN + BOOLEAN"))
+        );
+    }
+
+    #[test]
+    fn wrong_argument_count_is_an_error() {
+        assert_eq!(
+            eval("let add = fn(a, b) { a + b }; add(1);"),
+            Object::Error(String::from("wrong number of arguments: expected 2, got 1"))
+        );
+    }
+
+    #[test]
+    fn calling_a_non_function_is_an_error() {
+        assert_eq!(
+            eval("let x = 5; x();"),
+            Object::Error(String::from("not a function: INTEGER"))
+        );
+    }
+
+    #[test]
+    fn pipe_evaluates_left_before_right() {
+        // The left side errors with a type mismatch; the right side (an
+        // undefined identifier) would error differently if it were
+        // evaluated first. Getting the left-hand error back proves the
+        // operands are evaluated left-to-right, like every other infix
+        // expression.
+        assert_eq!(
+            eval("true + 1 |: undefinedFunc;"),
+            Object::Error(String::from("type mismatch: BOOLEAN + INTEGER"))
+        );
+    }
+
+    #[test]
+    fn pipe_calls_the_right_hand_side_with_the_left_value() {
+        assert_eq!(eval("5 |: fn(x) { x * x };"), Object::Integer(25));
+    }
+
+    #[test]
+    fn pipe_chains_left_associatively() {
+        let double = "fn(x) { x * 2 }";
+        assert_eq!(
+            eval(&format!("1 |: {} |: {};", double, double)),
+            Object::Integer(4)
+        );
+    }
+
+    #[test]
+    fn arrow_lambda_single_parameter() {
+        assert_eq!(eval("(x -> x * x)(5);"), Object::Integer(25));
+    }
+
+    #[test]
+    fn arrow_lambda_multiple_parameters() {
+        assert_eq!(eval("((a, b) -> a + b)(3, 4);"), Object::Integer(7));
+    }
+}
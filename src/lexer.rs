@@ -0,0 +1,255 @@
+use crate::token::Token;
+
+#[derive(Clone)]
+pub struct Lexer {
+    input: Vec<char>,
+    position: usize,
+}
+
+impl Lexer {
+    pub fn new(input: &str) -> Self {
+        Lexer {
+            input: input.chars().collect(),
+            position: 0,
+        }
+    }
+
+    pub fn next_token(&mut self) -> Token {
+        self.skip_whitespace();
+
+        if self.position >= self.input.len() {
+            return Token::Eof;
+        }
+
+        let ch = self.input[self.position];
+
+        let token = match ch {
+            '=' => {
+                if self.peek_char() == Some('=') {
+                    self.position += 1;
+                    Token::Eq
+                } else {
+                    Token::Assign
+                }
+            }
+            '!' => {
+                if self.peek_char() == Some('=') {
+                    self.position += 1;
+                    Token::NotEq
+                } else {
+                    Token::Bang
+                }
+            }
+            '+' => Token::Plus,
+            '-' => {
+                if self.peek_char() == Some('>') {
+                    self.position += 1;
+                    Token::Arrow
+                } else {
+                    Token::Minus
+                }
+            }
+            '|' => {
+                if self.peek_char() == Some(':') {
+                    self.position += 1;
+                    Token::Pipe
+                } else {
+                    Token::Illegal(ch.to_string())
+                }
+            }
+            '*' => Token::Asterisk,
+            '/' => Token::Slash,
+            '<' => Token::Lt,
+            '>' => Token::Gt,
+            ',' => Token::Comma,
+            ';' => Token::Semicolon,
+            ':' => Token::Colon,
+            '(' => Token::Lparen,
+            ')' => Token::Rparen,
+            '{' => Token::Lbrace,
+            '}' => Token::Rbrace,
+            '[' => Token::Lbracket,
+            ']' => Token::Rbracket,
+            '"' => return self.read_string(),
+            _ => {
+                if is_letter(ch) {
+                    return self.read_identifier();
+                } else if ch.is_ascii_digit() {
+                    return self.read_number();
+                } else {
+                    Token::Illegal(ch.to_string())
+                }
+            }
+        };
+
+        self.position += 1;
+        token
+    }
+
+    fn peek_char(&self) -> Option<char> {
+        self.input.get(self.position + 1).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(&ch) = self.input.get(self.position) {
+            if ch.is_whitespace() {
+                self.position += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn read_identifier(&mut self) -> Token {
+        let start = self.position;
+        while let Some(&ch) = self.input.get(self.position) {
+            if is_letter(ch) || ch.is_ascii_digit() {
+                self.position += 1;
+            } else {
+                break;
+            }
+        }
+
+        let ident: String = self.input[start..self.position].iter().collect();
+        Token::lookup_ident(&ident)
+    }
+
+    fn read_number(&mut self) -> Token {
+        let start = self.position;
+        while let Some(&ch) = self.input.get(self.position) {
+            if ch.is_ascii_digit() {
+                self.position += 1;
+            } else {
+                break;
+            }
+        }
+
+        let number: String = self.input[start..self.position].iter().collect();
+        match number.parse() {
+            Ok(value) => Token::Int(value),
+            Err(_) => Token::Illegal(number),
+        }
+    }
+
+    fn read_string(&mut self) -> Token {
+        // opening quote
+        self.position += 1;
+        let start = self.position;
+
+        while let Some(&ch) = self.input.get(self.position) {
+            if ch == '"' {
+                break;
+            }
+            self.position += 1;
+        }
+
+        let value: String = self.input[start..self.position].iter().collect();
+        // closing quote
+        self.position += 1;
+        Token::Str(value)
+    }
+}
+
+fn is_letter(ch: char) -> bool {
+    ch.is_alphabetic() || ch == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(input: &str) -> Vec<Token> {
+        let mut lexer = Lexer::new(input);
+        let mut tokens = Vec::new();
+        loop {
+            let token = lexer.next_token();
+            if token == Token::Eof {
+                break;
+            }
+            tokens.push(token);
+        }
+        tokens
+    }
+
+    #[test]
+    fn strings_arrays_and_hashes() {
+        let input = r#"
+            "foobar";
+            "foo bar";
+            [1, 2];
+            {"one": 1};
+        "#;
+
+        assert_eq!(
+            tokens(input),
+            vec![
+                Token::Str(String::from("foobar")),
+                Token::Semicolon,
+                Token::Str(String::from("foo bar")),
+                Token::Semicolon,
+                Token::Lbracket,
+                Token::Int(1),
+                Token::Comma,
+                Token::Int(2),
+                Token::Rbracket,
+                Token::Semicolon,
+                Token::Lbrace,
+                Token::Str(String::from("one")),
+                Token::Colon,
+                Token::Int(1),
+                Token::Rbrace,
+                Token::Semicolon,
+            ]
+        );
+    }
+
+    #[test]
+    fn an_integer_literal_that_overflows_i64_is_illegal() {
+        assert_eq!(
+            tokens("99999999999999999999;"),
+            vec![
+                Token::Illegal(String::from("99999999999999999999")),
+                Token::Semicolon,
+            ]
+        );
+    }
+}
+
+// Coverage for the chunk0-5 pipe operator and arrow-lambda syntax.
+#[cfg(test)]
+mod pipe_and_arrow_tests {
+    use super::*;
+
+    fn tokens(input: &str) -> Vec<Token> {
+        let mut lexer = Lexer::new(input);
+        let mut tokens = Vec::new();
+        loop {
+            let token = lexer.next_token();
+            if token == Token::Eof {
+                break;
+            }
+            tokens.push(token);
+        }
+        tokens
+    }
+
+    #[test]
+    fn pipe_and_arrow_tokens() {
+        assert_eq!(
+            tokens("x -> x |: f;"),
+            vec![
+                Token::Ident(String::from("x")),
+                Token::Arrow,
+                Token::Ident(String::from("x")),
+                Token::Pipe,
+                Token::Ident(String::from("f")),
+                Token::Semicolon,
+            ]
+        );
+    }
+
+    #[test]
+    fn a_lone_pipe_without_colon_is_illegal() {
+        assert_eq!(tokens("|"), vec![Token::Illegal(String::from("|"))]);
+    }
+}
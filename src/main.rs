@@ -1,17 +1,18 @@
-#![feature(box_patterns)]
-
-extern crate regex;
-
 mod ast;
+mod builtins;
+mod cli;
+mod code;
+mod compiler;
 mod lexer;
 mod parser;
 mod repl;
 mod token;
 mod object;
 mod evaluator;
-
-use crate::repl::Repl;
+mod environment;
+mod symbol_table;
+mod vm;
 
 fn main() {
-    Repl::run();
+    cli::run(std::env::args().collect());
 }
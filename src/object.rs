@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+use crate::ast::{BlockStatement, Identifier};
+use crate::code::Instructions;
+use crate::environment::Environment;
+
+#[derive(Debug, Clone)]
+pub enum Object {
+    Integer(i64),
+    Boolean(bool),
+    Null,
+    ReturnValue(Box<Object>),
+    Function {
+        parameters: Vec<Identifier>,
+        body: Rc<BlockStatement>,
+        env: Environment,
+    },
+    Error(String),
+    String(String),
+    Array(Vec<Object>),
+    Hash(HashMap<HashKey, HashPair>),
+    Builtin(BuiltinFn),
+    CompiledFunction {
+        instructions: Rc<Instructions>,
+        num_locals: usize,
+        num_parameters: usize,
+    },
+    Closure {
+        function: Rc<Object>,
+        free: Vec<Object>,
+    },
+}
+
+pub type BuiltinFn = fn(Vec<Object>) -> Object;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum HashKey {
+    Integer(i64),
+    Boolean(bool),
+    String(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct HashPair {
+    pub key: Object,
+    pub value: Object,
+}
+
+impl Object {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Object::Integer(_) => "INTEGER",
+            Object::Boolean(_) => "BOOLEAN",
+            Object::Null => "NULL",
+            Object::ReturnValue(_) => "RETURN_VALUE",
+            Object::Function { .. } => "FUNCTION",
+            Object::Error(_) => "ERROR",
+            Object::String(_) => "STRING",
+            Object::Array(_) => "ARRAY",
+            Object::Hash(_) => "HASH",
+            Object::Builtin(_) => "BUILTIN",
+            Object::CompiledFunction { .. } => "COMPILED_FUNCTION_OBJ",
+            Object::Closure { .. } => "CLOSURE",
+        }
+    }
+
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Object::Boolean(value) => *value,
+            Object::Null => false,
+            _ => true,
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        matches!(self, Object::Error(_))
+    }
+
+    pub fn hash_key(&self) -> Result<HashKey, String> {
+        match self {
+            Object::Integer(value) => Ok(HashKey::Integer(*value)),
+            Object::Boolean(value) => Ok(HashKey::Boolean(*value)),
+            Object::String(value) => Ok(HashKey::String(value.clone())),
+            _ => Err(format!("unusable as hash key: {}", self.type_name())),
+        }
+    }
+}
+
+impl PartialEq for Object {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Object::Integer(a), Object::Integer(b)) => a == b,
+            (Object::Boolean(a), Object::Boolean(b)) => a == b,
+            (Object::Null, Object::Null) => true,
+            (Object::ReturnValue(a), Object::ReturnValue(b)) => a == b,
+            (Object::Error(a), Object::Error(b)) => a == b,
+            (Object::String(a), Object::String(b)) => a == b,
+            (Object::Array(a), Object::Array(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Object {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Object::Integer(value) => write!(f, "{}", value),
+            Object::Boolean(value) => write!(f, "{}", value),
+            Object::Null => write!(f, "null"),
+            Object::ReturnValue(value) => write!(f, "{}", value),
+            Object::Function { parameters, body, .. } => {
+                write!(f, "fn({}) {:?}", parameters.join(", "), body)
+            }
+            Object::Error(message) => write!(f, "ERROR: {}", message),
+            Object::String(value) => write!(f, "{}", value),
+            Object::Array(elements) => {
+                let elements = elements
+                    .iter()
+                    .map(|element| format!("{}", element))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                write!(f, "[{}]", elements)
+            }
+            Object::Hash(pairs) => {
+                let pairs = pairs
+                    .values()
+                    .map(|pair| format!("{}: {}", pair.key, pair.value))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                write!(f, "{{{}}}", pairs)
+            }
+            Object::Builtin(_) => write!(f, "builtin function"),
+            Object::CompiledFunction { .. } => write!(f, "compiled function"),
+            Object::Closure { .. } => write!(f, "closure"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers_booleans_and_strings_hash_by_value() {
+        assert_eq!(
+            Object::Integer(5).hash_key(),
+            Object::Integer(5).hash_key()
+        );
+        assert_eq!(
+            Object::String(String::from("hello")).hash_key(),
+            Object::String(String::from("hello")).hash_key()
+        );
+        assert_ne!(
+            Object::String(String::from("hello")).hash_key(),
+            Object::String(String::from("world")).hash_key()
+        );
+        assert_eq!(
+            Object::Boolean(true).hash_key(),
+            Object::Boolean(true).hash_key()
+        );
+    }
+
+    #[test]
+    fn arrays_and_functions_are_unusable_as_hash_keys() {
+        assert_eq!(
+            Object::Array(vec![]).hash_key(),
+            Err(String::from("unusable as hash key: ARRAY"))
+        );
+        assert_eq!(
+            Object::Builtin(|_| Object::Null).hash_key(),
+            Err(String::from("unusable as hash key: BUILTIN"))
+        );
+    }
+}
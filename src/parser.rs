@@ -0,0 +1,538 @@
+use crate::ast::{BlockStatement, Expression, Program, Statement};
+use crate::lexer::Lexer;
+use crate::token::Token;
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+enum Precedence {
+    Lowest,
+    Pipe,
+    Equals,
+    LessGreater,
+    Sum,
+    Product,
+    Prefix,
+    Call,
+    Index,
+}
+
+fn precedence_of(token: &Token) -> Precedence {
+    match token {
+        Token::Pipe => Precedence::Pipe,
+        Token::Eq | Token::NotEq => Precedence::Equals,
+        Token::Lt | Token::Gt => Precedence::LessGreater,
+        Token::Plus | Token::Minus => Precedence::Sum,
+        Token::Slash | Token::Asterisk => Precedence::Product,
+        Token::Lparen => Precedence::Call,
+        Token::Lbracket => Precedence::Index,
+        _ => Precedence::Lowest,
+    }
+}
+
+pub struct Parser {
+    lexer: Lexer,
+    current_token: Token,
+    peek_token: Token,
+    errors: Vec<String>,
+}
+
+struct ParserCheckpoint {
+    lexer: Lexer,
+    current_token: Token,
+    peek_token: Token,
+    errors_len: usize,
+}
+
+impl Parser {
+    pub fn new(mut lexer: Lexer) -> Self {
+        let current_token = lexer.next_token();
+        let peek_token = lexer.next_token();
+
+        Parser {
+            lexer,
+            current_token,
+            peek_token,
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn errors(&self) -> &[String] {
+        &self.errors
+    }
+
+    fn next_token(&mut self) {
+        self.current_token = self.peek_token.clone();
+        self.peek_token = self.lexer.next_token();
+    }
+
+    pub fn parse_program(&mut self) -> Program {
+        let mut statements = Vec::new();
+
+        while self.current_token != Token::Eof {
+            if let Some(statement) = self.parse_statement() {
+                statements.push(statement);
+            }
+            self.next_token();
+        }
+
+        Program { statements }
+    }
+
+    fn parse_statement(&mut self) -> Option<Statement> {
+        match self.current_token {
+            Token::Let => self.parse_let_statement(),
+            Token::Return => self.parse_return_statement(),
+            _ => self.parse_expression_statement(),
+        }
+    }
+
+    fn parse_let_statement(&mut self) -> Option<Statement> {
+        let name = match &self.peek_token {
+            Token::Ident(name) => name.clone(),
+            _ => {
+                self.peek_error(&Token::Ident(String::new()));
+                return None;
+            }
+        };
+        self.next_token();
+
+        if !self.expect_peek(Token::Assign) {
+            return None;
+        }
+        self.next_token();
+
+        let value = self.parse_expression(Precedence::Lowest)?;
+
+        if self.peek_token == Token::Semicolon {
+            self.next_token();
+        }
+
+        Some(Statement::new_let(&name, value))
+    }
+
+    fn parse_return_statement(&mut self) -> Option<Statement> {
+        self.next_token();
+
+        let value = self.parse_expression(Precedence::Lowest)?;
+
+        if self.peek_token == Token::Semicolon {
+            self.next_token();
+        }
+
+        Some(Statement::new_return(value))
+    }
+
+    fn parse_expression_statement(&mut self) -> Option<Statement> {
+        let expression = self.parse_expression(Precedence::Lowest)?;
+
+        if self.peek_token == Token::Semicolon {
+            self.next_token();
+        }
+
+        Some(Statement::new_expression(expression))
+    }
+
+    fn parse_expression(&mut self, precedence: Precedence) -> Option<Expression> {
+        let mut left = self.parse_prefix()?;
+
+        while self.peek_token != Token::Semicolon && precedence < precedence_of(&self.peek_token) {
+            self.next_token();
+            left = self.parse_infix(left)?;
+        }
+
+        Some(left)
+    }
+
+    fn parse_prefix(&mut self) -> Option<Expression> {
+        match self.current_token.clone() {
+            Token::Ident(name) => self.parse_identifier_or_arrow_lambda(name),
+            Token::Int(value) => Some(Expression::new_integer(value)),
+            Token::Str(value) => Some(Expression::new_string(&value)),
+            Token::True => Some(Expression::new_boolean(true)),
+            Token::False => Some(Expression::new_boolean(false)),
+            Token::Bang | Token::Minus => self.parse_prefix_expression(),
+            Token::Lparen => self.parse_grouped_expression_or_arrow_lambda(),
+            Token::If => self.parse_if_expression(),
+            Token::Function => self.parse_function_literal(),
+            Token::Lbracket => self.parse_array_literal(),
+            Token::Lbrace => self.parse_hash_literal(),
+            token => {
+                self.errors
+                    .push(format!("no prefix parse function for {:?} found", token));
+                None
+            }
+        }
+    }
+
+    fn parse_infix(&mut self, left: Expression) -> Option<Expression> {
+        match self.current_token {
+            Token::Lparen => self.parse_call_expression(left),
+            Token::Lbracket => self.parse_index_expression(left),
+            _ => self.parse_infix_expression(left),
+        }
+    }
+
+    fn parse_prefix_expression(&mut self) -> Option<Expression> {
+        let operator = operator_literal(&self.current_token);
+        self.next_token();
+        let right = self.parse_expression(Precedence::Prefix)?;
+        Some(Expression::new_prefix(&operator, right))
+    }
+
+    fn parse_infix_expression(&mut self, left: Expression) -> Option<Expression> {
+        let operator = operator_literal(&self.current_token);
+        let precedence = precedence_of(&self.current_token);
+        self.next_token();
+        let right = self.parse_expression(precedence)?;
+        Some(Expression::new_infix(&operator, left, right))
+    }
+
+    fn parse_grouped_expression(&mut self) -> Option<Expression> {
+        self.next_token();
+        let expression = self.parse_expression(Precedence::Lowest)?;
+
+        if !self.expect_peek(Token::Rparen) {
+            return None;
+        }
+
+        Some(expression)
+    }
+
+    fn parse_identifier_or_arrow_lambda(&mut self, name: String) -> Option<Expression> {
+        if self.peek_token == Token::Arrow {
+            self.next_token();
+            return self.parse_arrow_lambda_body(vec![name]);
+        }
+
+        Some(Expression::new_identifier(&name))
+    }
+
+    /// `(a, b) -> a + b` looks exactly like a parenthesized expression until the
+    /// closing paren, so we speculatively parse a parameter list and roll back
+    /// to an ordinary grouped expression if it isn't followed by `->`.
+    fn parse_grouped_expression_or_arrow_lambda(&mut self) -> Option<Expression> {
+        let checkpoint = self.checkpoint();
+
+        if let Some(parameters) = self.parse_function_parameters() {
+            if self.peek_token == Token::Arrow {
+                self.next_token();
+                return self.parse_arrow_lambda_body(parameters);
+            }
+        }
+
+        self.restore(checkpoint);
+        self.parse_grouped_expression()
+    }
+
+    fn parse_arrow_lambda_body(&mut self, parameters: Vec<String>) -> Option<Expression> {
+        self.next_token();
+        let body_expression = self.parse_expression(Precedence::Lowest)?;
+        let body = BlockStatement::new(vec![Statement::new_expression(body_expression)]);
+        Some(Expression::new_function_literal(parameters, body))
+    }
+
+    fn checkpoint(&self) -> ParserCheckpoint {
+        ParserCheckpoint {
+            lexer: self.lexer.clone(),
+            current_token: self.current_token.clone(),
+            peek_token: self.peek_token.clone(),
+            errors_len: self.errors.len(),
+        }
+    }
+
+    fn restore(&mut self, checkpoint: ParserCheckpoint) {
+        self.lexer = checkpoint.lexer;
+        self.current_token = checkpoint.current_token;
+        self.peek_token = checkpoint.peek_token;
+        self.errors.truncate(checkpoint.errors_len);
+    }
+
+    fn parse_if_expression(&mut self) -> Option<Expression> {
+        if !self.expect_peek(Token::Lparen) {
+            return None;
+        }
+        self.next_token();
+
+        let condition = self.parse_expression(Precedence::Lowest)?;
+
+        if !self.expect_peek(Token::Rparen) {
+            return None;
+        }
+        if !self.expect_peek(Token::Lbrace) {
+            return None;
+        }
+
+        let consequence = self.parse_block_statement();
+
+        let alternative = if self.peek_token == Token::Else {
+            self.next_token();
+
+            if !self.expect_peek(Token::Lbrace) {
+                return None;
+            }
+
+            Some(self.parse_block_statement())
+        } else {
+            None
+        };
+
+        Some(Expression::new_if(condition, consequence, alternative))
+    }
+
+    fn parse_block_statement(&mut self) -> BlockStatement {
+        let mut statements = Vec::new();
+        self.next_token();
+
+        while self.current_token != Token::Rbrace && self.current_token != Token::Eof {
+            if let Some(statement) = self.parse_statement() {
+                statements.push(statement);
+            }
+            self.next_token();
+        }
+
+        BlockStatement::new(statements)
+    }
+
+    fn parse_function_literal(&mut self) -> Option<Expression> {
+        if !self.expect_peek(Token::Lparen) {
+            return None;
+        }
+
+        let parameters = self.parse_function_parameters()?;
+
+        if !self.expect_peek(Token::Lbrace) {
+            return None;
+        }
+
+        let body = self.parse_block_statement();
+
+        Some(Expression::new_function_literal(parameters, body))
+    }
+
+    fn parse_function_parameters(&mut self) -> Option<Vec<String>> {
+        let mut identifiers = Vec::new();
+
+        if self.peek_token == Token::Rparen {
+            self.next_token();
+            return Some(identifiers);
+        }
+
+        self.next_token();
+
+        match &self.current_token {
+            Token::Ident(name) => identifiers.push(name.clone()),
+            _ => return None,
+        }
+
+        while self.peek_token == Token::Comma {
+            self.next_token();
+            self.next_token();
+
+            match &self.current_token {
+                Token::Ident(name) => identifiers.push(name.clone()),
+                _ => return None,
+            }
+        }
+
+        if !self.expect_peek(Token::Rparen) {
+            return None;
+        }
+
+        Some(identifiers)
+    }
+
+    fn parse_call_expression(&mut self, function: Expression) -> Option<Expression> {
+        let arguments = self.parse_expression_list(Token::Rparen)?;
+        Some(Expression::new_call_expression(function, arguments))
+    }
+
+    fn parse_array_literal(&mut self) -> Option<Expression> {
+        let elements = self.parse_expression_list(Token::Rbracket)?;
+        Some(Expression::new_array(elements))
+    }
+
+    fn parse_hash_literal(&mut self) -> Option<Expression> {
+        let mut pairs = Vec::new();
+
+        while self.peek_token != Token::Rbrace {
+            self.next_token();
+            let key = self.parse_expression(Precedence::Lowest)?;
+
+            if !self.expect_peek(Token::Colon) {
+                return None;
+            }
+            self.next_token();
+
+            let value = self.parse_expression(Precedence::Lowest)?;
+            pairs.push((key, value));
+
+            if self.peek_token != Token::Rbrace && !self.expect_peek(Token::Comma) {
+                return None;
+            }
+        }
+
+        if !self.expect_peek(Token::Rbrace) {
+            return None;
+        }
+
+        Some(Expression::new_hash(pairs))
+    }
+
+    fn parse_index_expression(&mut self, left: Expression) -> Option<Expression> {
+        self.next_token();
+        let index = self.parse_expression(Precedence::Lowest)?;
+
+        if !self.expect_peek(Token::Rbracket) {
+            return None;
+        }
+
+        Some(Expression::new_index(left, index))
+    }
+
+    fn parse_expression_list(&mut self, end: Token) -> Option<Vec<Expression>> {
+        let mut list = Vec::new();
+
+        if self.peek_token == end {
+            self.next_token();
+            return Some(list);
+        }
+
+        self.next_token();
+        list.push(self.parse_expression(Precedence::Lowest)?);
+
+        while self.peek_token == Token::Comma {
+            self.next_token();
+            self.next_token();
+            list.push(self.parse_expression(Precedence::Lowest)?);
+        }
+
+        if !self.expect_peek(end) {
+            return None;
+        }
+
+        Some(list)
+    }
+
+    fn expect_peek(&mut self, token: Token) -> bool {
+        if self.peek_token == token {
+            self.next_token();
+            true
+        } else {
+            self.peek_error(&token);
+            false
+        }
+    }
+
+    fn peek_error(&mut self, expected: &Token) {
+        self.errors.push(format!(
+            "expected next token to be {:?}, got {:?} instead",
+            expected, self.peek_token
+        ));
+    }
+}
+
+fn operator_literal(token: &Token) -> String {
+    match token {
+        Token::Bang => "!".to_string(),
+        Token::Minus => "-".to_string(),
+        Token::Plus => "+".to_string(),
+        Token::Asterisk => "*".to_string(),
+        Token::Slash => "/".to_string(),
+        Token::Lt => "<".to_string(),
+        Token::Gt => ">".to_string(),
+        Token::Eq => "==".to_string(),
+        Token::NotEq => "!=".to_string(),
+        Token::Pipe => "|:".to_string(),
+        other => format!("{:?}", other),
+    }
+}
+
+#[cfg(test)]
+fn parse(input: &str) -> Program {
+    let lexer = Lexer::new(input);
+    let mut parser = Parser::new(lexer);
+    let program = parser.parse_program();
+    assert!(parser.errors().is_empty(), "parser errors: {:?}", parser.errors());
+    program
+}
+
+#[cfg(test)]
+fn first_expression(program: &Program) -> &Expression {
+    match &program.statements[0] {
+        Statement::Expression(expression) => expression,
+        other => panic!("expected an expression statement, got {:?}", other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn string_literal() {
+        let program = parse(r#""hello world";"#);
+        assert_eq!(
+            *first_expression(&program),
+            Expression::new_string("hello world")
+        );
+    }
+
+    #[test]
+    fn array_literal() {
+        let program = parse("[1, 2 * 2, 3 + 3];");
+        assert_eq!(format!("{}", first_expression(&program)), "[1, (2 * 2), (3 + 3)]");
+    }
+
+    #[test]
+    fn hash_literal() {
+        let program = parse(r#"{"one": 1, "two": 2};"#);
+        assert_eq!(format!("{}", first_expression(&program)), "{\"one\": 1, \"two\": 2}");
+    }
+
+    #[test]
+    fn empty_hash_literal() {
+        let program = parse("{};");
+        assert_eq!(format!("{}", first_expression(&program)), "{}");
+    }
+
+    #[test]
+    fn index_expression() {
+        let program = parse("myArray[1 + 1];");
+        assert_eq!(format!("{}", first_expression(&program)), "(myArray[(1 + 1)])");
+    }
+}
+
+// Coverage for the chunk0-5 pipe operator and arrow-lambda syntax.
+#[cfg(test)]
+mod pipe_and_arrow_tests {
+    use super::*;
+
+    #[test]
+    fn pipe_has_lower_precedence_than_comparison() {
+        let program = parse("a |: b == c;");
+        assert_eq!(format!("{}", first_expression(&program)), "(a |: (b == c))");
+    }
+
+    #[test]
+    fn pipe_is_left_associative() {
+        let program = parse("a |: b |: c;");
+        assert_eq!(format!("{}", first_expression(&program)), "((a |: b) |: c)");
+    }
+
+    #[test]
+    fn single_parameter_arrow_lambda() {
+        let program = parse("x -> x * x;");
+        assert_eq!(format!("{}", first_expression(&program)), "fn (x) {(x * x)}");
+    }
+
+    #[test]
+    fn multi_parameter_arrow_lambda() {
+        let program = parse("(a, b) -> a + b;");
+        assert_eq!(format!("{}", first_expression(&program)), "fn (a, b) {(a + b)}");
+    }
+
+    #[test]
+    fn parenthesized_expression_is_not_mistaken_for_a_lambda() {
+        let program = parse("(a + b) * c;");
+        assert_eq!(format!("{}", first_expression(&program)), "((a + b) * c)");
+    }
+}
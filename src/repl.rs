@@ -0,0 +1,165 @@
+use std::path::PathBuf;
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
+use crate::environment::Environment;
+use crate::evaluator;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+const PROMPT: &str = "\x1b[32m>> \x1b[0m";
+const CONTINUATION_PROMPT: &str = "\x1b[33m.. \x1b[0m";
+
+pub struct Repl;
+
+impl Repl {
+    pub fn run() {
+        let mut env = Environment::new_global();
+        let mut editor = DefaultEditor::new().expect("failed to initialize line editor");
+        let history_path = history_path();
+        editor.load_history(&history_path).ok();
+
+        while let Some(source) = read_statement(&mut editor) {
+            if source.trim().is_empty() {
+                continue;
+            }
+
+            editor.add_history_entry(source.replace('\n', " ")).ok();
+
+            let lexer = Lexer::new(&source);
+            let mut parser = Parser::new(lexer);
+            let program = parser.parse_program();
+
+            if !parser.errors().is_empty() {
+                print_parse_errors(parser.errors());
+                continue;
+            }
+
+            let result = evaluator::eval_program(&program, &mut env);
+            println!("{}", result);
+        }
+
+        editor.save_history(&history_path).ok();
+    }
+}
+
+/// Reads lines until the accumulated source has balanced brackets and
+/// doesn't trail a binary operator, so multi-line `fn`/`if` bodies can be
+/// typed interactively instead of failing to parse line-by-line.
+fn read_statement(editor: &mut DefaultEditor) -> Option<String> {
+    let mut buffer = String::new();
+    let mut prompt = PROMPT;
+
+    loop {
+        match editor.readline(prompt) {
+            Ok(line) => {
+                buffer.push_str(&line);
+                buffer.push('\n');
+            }
+            Err(ReadlineError::Interrupted) | Err(ReadlineError::Eof) => return None,
+            Err(_) => return None,
+        }
+
+        if is_complete(&buffer) {
+            return Some(buffer);
+        }
+        prompt = CONTINUATION_PROMPT;
+    }
+}
+
+fn is_complete(source: &str) -> bool {
+    brackets_balanced(source) && !ends_with_operator(source)
+}
+
+fn brackets_balanced(source: &str) -> bool {
+    let mut depth = 0i32;
+    for ch in source.chars() {
+        match ch {
+            '{' | '(' | '[' => depth += 1,
+            '}' | ')' | ']' => depth -= 1,
+            _ => {}
+        }
+    }
+    depth <= 0
+}
+
+fn ends_with_operator(source: &str) -> bool {
+    let trimmed = source.trim_end();
+    if trimmed.is_empty() {
+        return false;
+    }
+
+    const MULTI_CHAR_OPERATORS: &[&str] = &["==", "!=", "->", "|:"];
+    if MULTI_CHAR_OPERATORS.iter().any(|op| trimmed.ends_with(op)) {
+        return true;
+    }
+
+    matches!(
+        trimmed.chars().last(),
+        Some('+' | '-' | '*' | '/' | '<' | '>' | '=' | ',')
+    )
+}
+
+fn print_parse_errors(errors: &[String]) {
+    for error in errors {
+        println!("\t{}", error);
+    }
+}
+
+fn history_path() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| String::from("."));
+    PathBuf::from(home).join(".monkey_history")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_single_complete_statement_is_complete() {
+        assert!(is_complete("let x = 5;\n"));
+    }
+
+    #[test]
+    fn unbalanced_brackets_are_incomplete() {
+        assert!(!brackets_balanced("fn(x) {\n"));
+        assert!(!brackets_balanced("[1, 2\n"));
+        assert!(!is_complete("if (x) {\n"));
+    }
+
+    #[test]
+    fn a_multiline_function_body_is_complete_once_closed() {
+        let source = "let add = fn(x, y) {\n  x + y\n};\n";
+        assert!(is_complete(source));
+    }
+
+    #[test]
+    fn extra_closing_brackets_are_still_complete() {
+        assert!(brackets_balanced("}\n"));
+    }
+
+    #[test]
+    fn a_trailing_binary_operator_is_incomplete() {
+        for op in ["+", "-", "*", "/", "<", ">", "="] {
+            assert!(ends_with_operator(&format!("x {}\n", op)));
+            assert!(!is_complete(&format!("x {}\n", op)));
+        }
+    }
+
+    #[test]
+    fn a_trailing_multi_char_operator_is_incomplete() {
+        for op in ["==", "!=", "->", "|:"] {
+            assert!(ends_with_operator(&format!("x {}\n", op)));
+        }
+    }
+
+    #[test]
+    fn a_trailing_comma_is_incomplete() {
+        assert!(ends_with_operator("f(1,\n"));
+    }
+
+    #[test]
+    fn an_empty_buffer_does_not_end_with_an_operator() {
+        assert!(!ends_with_operator(""));
+        assert!(!ends_with_operator("   \n"));
+    }
+}
@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Scope {
+    Global,
+    Local,
+    Free,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Symbol {
+    pub index: usize,
+    pub scope: Scope,
+}
+
+pub struct SymbolTable {
+    outer: Option<Box<SymbolTable>>,
+    store: HashMap<String, Symbol>,
+    definitions: usize,
+    pub free_symbols: Vec<Symbol>,
+}
+
+impl SymbolTable {
+    pub fn new() -> Self {
+        SymbolTable {
+            outer: None,
+            store: HashMap::new(),
+            definitions: 0,
+            free_symbols: Vec::new(),
+        }
+    }
+
+    pub fn new_enclosed(outer: SymbolTable) -> Self {
+        SymbolTable {
+            outer: Some(Box::new(outer)),
+            store: HashMap::new(),
+            definitions: 0,
+            free_symbols: Vec::new(),
+        }
+    }
+
+    pub fn into_outer(self) -> Option<SymbolTable> {
+        self.outer.map(|outer| *outer)
+    }
+
+    pub fn define(&mut self, name: &str) -> Symbol {
+        let scope = if self.outer.is_some() {
+            Scope::Local
+        } else {
+            Scope::Global
+        };
+
+        let symbol = Symbol {
+            index: self.definitions,
+            scope,
+        };
+        self.store.insert(String::from(name), symbol);
+        self.definitions += 1;
+        symbol
+    }
+
+    /// Records that `name` is captured from an enclosing scope, giving it a
+    /// slot in this scope's free-variable list so the compiler can emit
+    /// `OpGetFree` for it instead of treating it as a local.
+    fn define_free(&mut self, name: &str, original: Symbol) -> Symbol {
+        self.free_symbols.push(original);
+        let symbol = Symbol {
+            index: self.free_symbols.len() - 1,
+            scope: Scope::Free,
+        };
+        self.store.insert(String::from(name), symbol);
+        symbol
+    }
+
+    pub fn len(&self) -> usize {
+        self.definitions
+    }
+
+    /// Looks `name` up in this scope, then walks outward through enclosing
+    /// scopes. A name found in an outer local or free scope is recorded as a
+    /// free variable here (and in every scope in between), since its value
+    /// has to be captured at closure-creation time rather than read directly
+    /// off another frame's stack slots. Globals need no such capture, since
+    /// they're addressable from anywhere by index.
+    pub fn resolve(&mut self, name: &str) -> Option<Symbol> {
+        if let Some(symbol) = self.store.get(name).copied() {
+            return Some(symbol);
+        }
+
+        let outer_symbol = self.outer.as_mut()?.resolve(name)?;
+
+        match outer_symbol.scope {
+            Scope::Global => Some(outer_symbol),
+            Scope::Local | Scope::Free => Some(self.define_free(name, outer_symbol)),
+        }
+    }
+}
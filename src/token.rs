@@ -0,0 +1,61 @@
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    Illegal(String),
+    Eof,
+
+    // Identifiers + literals
+    Ident(String),
+    Int(i64),
+    Str(String),
+
+    // Operators
+    Assign,
+    Plus,
+    Minus,
+    Bang,
+    Asterisk,
+    Slash,
+
+    Lt,
+    Gt,
+    Eq,
+    NotEq,
+    Arrow,
+    Pipe,
+
+    // Delimiters
+    Comma,
+    Semicolon,
+    Colon,
+
+    Lparen,
+    Rparen,
+    Lbrace,
+    Rbrace,
+    Lbracket,
+    Rbracket,
+
+    // Keywords
+    Function,
+    Let,
+    True,
+    False,
+    If,
+    Else,
+    Return,
+}
+
+impl Token {
+    pub fn lookup_ident(ident: &str) -> Token {
+        match ident {
+            "fn" => Token::Function,
+            "let" => Token::Let,
+            "true" => Token::True,
+            "false" => Token::False,
+            "if" => Token::If,
+            "else" => Token::Else,
+            "return" => Token::Return,
+            _ => Token::Ident(String::from(ident)),
+        }
+    }
+}
@@ -0,0 +1,376 @@
+use std::rc::Rc;
+use crate::code::{self, Instructions, Opcode};
+use crate::compiler::Bytecode;
+use crate::object::Object;
+
+const STACK_SIZE: usize = 2048;
+
+struct Frame {
+    instructions: Rc<Instructions>,
+    ip: usize,
+    base_pointer: usize,
+    free: Vec<Object>,
+}
+
+pub struct VM {
+    constants: Vec<Object>,
+    stack: Vec<Object>,
+    globals: Vec<Object>,
+    frames: Vec<Frame>,
+    last_popped: Object,
+}
+
+impl VM {
+    pub fn new(bytecode: Bytecode) -> Self {
+        let main_frame = Frame {
+            instructions: Rc::new(bytecode.instructions),
+            ip: 0,
+            base_pointer: 0,
+            free: Vec::new(),
+        };
+
+        VM {
+            constants: bytecode.constants,
+            stack: Vec::new(),
+            globals: Vec::new(),
+            frames: vec![main_frame],
+            last_popped: Object::Null,
+        }
+    }
+
+    pub fn last_popped_stack_element(&self) -> &Object {
+        &self.last_popped
+    }
+
+    pub fn run(&mut self) -> Result<(), String> {
+        while self.current_frame().ip < self.current_frame().instructions.len() {
+            let instructions = Rc::clone(&self.current_frame().instructions);
+            let ip = self.current_frame().ip;
+            let opcode = Opcode::from_byte(instructions[ip])
+                .ok_or_else(|| format!("unknown opcode byte {}", instructions[ip]))?;
+            self.current_frame_mut().ip += 1;
+
+            match opcode {
+                Opcode::Constant => {
+                    let index = self.read_u16(&instructions) as usize;
+                    let constant = self.constants[index].clone();
+                    self.push(constant)?;
+                }
+                Opcode::Add | Opcode::Sub | Opcode::Mul | Opcode::Div => {
+                    self.execute_binary_operation(opcode)?;
+                }
+                Opcode::True => self.push(Object::Boolean(true))?,
+                Opcode::False => self.push(Object::Boolean(false))?,
+                Opcode::Null => self.push(Object::Null)?,
+                Opcode::Equal | Opcode::NotEqual | Opcode::GreaterThan => {
+                    self.execute_comparison(opcode)?;
+                }
+                Opcode::Bang => self.execute_bang()?,
+                Opcode::Minus => self.execute_minus()?,
+                Opcode::Jump => {
+                    let target = self.read_u16(&instructions) as usize;
+                    self.current_frame_mut().ip = target;
+                }
+                Opcode::JumpNotTruthy => {
+                    let target = self.read_u16(&instructions) as usize;
+                    let condition = self.pop()?;
+                    if !condition.is_truthy() {
+                        self.current_frame_mut().ip = target;
+                    }
+                }
+                Opcode::SetGlobal => {
+                    let index = self.read_u16(&instructions) as usize;
+                    let value = self.pop()?;
+                    if index >= self.globals.len() {
+                        self.globals.resize(index + 1, Object::Null);
+                    }
+                    self.globals[index] = value;
+                }
+                Opcode::GetGlobal => {
+                    let index = self.read_u16(&instructions) as usize;
+                    let value = self.globals.get(index).cloned().unwrap_or(Object::Null);
+                    self.push(value)?;
+                }
+                Opcode::SetLocal => {
+                    let index = self.read_u8(&instructions) as usize;
+                    let base_pointer = self.current_frame().base_pointer;
+                    let value = self.pop()?;
+                    self.stack[base_pointer + index] = value;
+                }
+                Opcode::GetLocal => {
+                    let index = self.read_u8(&instructions) as usize;
+                    let base_pointer = self.current_frame().base_pointer;
+                    let value = self.stack[base_pointer + index].clone();
+                    self.push(value)?;
+                }
+                Opcode::Array => {
+                    let count = self.read_u16(&instructions) as usize;
+                    let start = self.stack.len() - count;
+                    let elements = self.stack.split_off(start);
+                    self.push(Object::Array(elements))?;
+                }
+                Opcode::Call => {
+                    let num_args = self.read_u8(&instructions) as usize;
+                    self.call_function(num_args)?;
+                }
+                Opcode::ReturnValue => {
+                    let return_value = self.pop()?;
+                    // A bare top-level `return` isn't inside any call frame
+                    // (base_pointer 0 belongs to the outermost frame), so
+                    // there's no caller frame to unwind into — just stop the
+                    // program with this value, matching the tree-walker.
+                    if self.frames.len() == 1 {
+                        self.push(return_value)?;
+                        return Ok(());
+                    }
+                    let frame = self.frames.pop().expect("return with no call frame");
+                    self.stack.truncate(frame.base_pointer - 1);
+                    self.push(return_value)?;
+                }
+                Opcode::Return => {
+                    if self.frames.len() == 1 {
+                        self.push(Object::Null)?;
+                        return Ok(());
+                    }
+                    let frame = self.frames.pop().expect("return with no call frame");
+                    self.stack.truncate(frame.base_pointer - 1);
+                    self.push(Object::Null)?;
+                }
+                Opcode::Pop => {
+                    self.pop()?;
+                }
+                Opcode::Closure => {
+                    let const_index = self.read_u16(&instructions) as usize;
+                    let num_free = self.read_u8(&instructions) as usize;
+                    let function = self.constants[const_index].clone();
+                    let free_start = self.stack.len() - num_free;
+                    let free = self.stack.split_off(free_start);
+                    self.push(Object::Closure {
+                        function: Rc::new(function),
+                        free,
+                    })?;
+                }
+                Opcode::GetFree => {
+                    let index = self.read_u8(&instructions) as usize;
+                    let value = self.current_frame().free[index].clone();
+                    self.push(value)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn call_function(&mut self, num_args: usize) -> Result<(), String> {
+        let callee_index = self.stack.len() - 1 - num_args;
+
+        match self.stack[callee_index].clone() {
+            Object::Closure { function, free } => {
+                let (instructions, num_locals, num_parameters) = match function.as_ref() {
+                    Object::CompiledFunction {
+                        instructions,
+                        num_locals,
+                        num_parameters,
+                    } => (Rc::clone(instructions), *num_locals, *num_parameters),
+                    other => {
+                        return Err(format!("closure wraps non-function: {}", other.type_name()))
+                    }
+                };
+
+                if num_parameters != num_args {
+                    return Err(format!(
+                        "wrong number of arguments: expected {}, got {}",
+                        num_parameters, num_args
+                    ));
+                }
+
+                let base_pointer = self.stack.len() - num_args;
+                for _ in num_args..num_locals {
+                    self.stack.push(Object::Null);
+                }
+
+                self.frames.push(Frame {
+                    instructions,
+                    ip: 0,
+                    base_pointer,
+                    free,
+                });
+
+                Ok(())
+            }
+            other => Err(format!("calling non-function: {}", other.type_name())),
+        }
+    }
+
+    fn execute_binary_operation(&mut self, op: Opcode) -> Result<(), String> {
+        let right = self.pop()?;
+        let left = self.pop()?;
+
+        match (left, right) {
+            (Object::Integer(left), Object::Integer(right)) => {
+                let result = match op {
+                    Opcode::Add => left.checked_add(right),
+                    Opcode::Sub => left.checked_sub(right),
+                    Opcode::Mul => left.checked_mul(right),
+                    Opcode::Div => {
+                        if right == 0 {
+                            return Err(String::from("division by zero"));
+                        }
+                        left.checked_div(right)
+                    }
+                    _ => unreachable!("execute_binary_operation called with non-arithmetic opcode"),
+                };
+                let result = result.ok_or_else(|| String::from("integer overflow"))?;
+                self.push(Object::Integer(result))
+            }
+            (left, right) => Err(format!(
+                "unsupported types for binary operation: {} {}",
+                left.type_name(),
+                right.type_name()
+            )),
+        }
+    }
+
+    fn execute_comparison(&mut self, op: Opcode) -> Result<(), String> {
+        let right = self.pop()?;
+        let left = self.pop()?;
+
+        if let (Object::Integer(left), Object::Integer(right)) = (&left, &right) {
+            let result = match op {
+                Opcode::Equal => left == right,
+                Opcode::NotEqual => left != right,
+                Opcode::GreaterThan => left > right,
+                _ => unreachable!("execute_comparison called with non-comparison opcode"),
+            };
+            return self.push(Object::Boolean(result));
+        }
+
+        match op {
+            Opcode::Equal => self.push(Object::Boolean(left == right)),
+            Opcode::NotEqual => self.push(Object::Boolean(left != right)),
+            _ => Err(format!(
+                "unsupported types for comparison: {} {}",
+                left.type_name(),
+                right.type_name()
+            )),
+        }
+    }
+
+    fn execute_bang(&mut self) -> Result<(), String> {
+        let operand = self.pop()?;
+        self.push(Object::Boolean(!operand.is_truthy()))
+    }
+
+    fn execute_minus(&mut self) -> Result<(), String> {
+        let operand = self.pop()?;
+        match operand {
+            Object::Integer(value) => self.push(Object::Integer(-value)),
+            other => Err(format!("unsupported type for negation: {}", other.type_name())),
+        }
+    }
+
+    fn current_frame(&self) -> &Frame {
+        self.frames.last().expect("vm always has a call frame")
+    }
+
+    fn current_frame_mut(&mut self) -> &mut Frame {
+        self.frames.last_mut().expect("vm always has a call frame")
+    }
+
+    fn read_u16(&mut self, instructions: &Instructions) -> u16 {
+        let value = code::read_u16(instructions, self.current_frame().ip);
+        self.current_frame_mut().ip += 2;
+        value
+    }
+
+    fn read_u8(&mut self, instructions: &Instructions) -> u8 {
+        let value = code::read_u8(instructions, self.current_frame().ip);
+        self.current_frame_mut().ip += 1;
+        value
+    }
+
+    fn push(&mut self, object: Object) -> Result<(), String> {
+        if self.stack.len() >= STACK_SIZE {
+            return Err(String::from("stack overflow"));
+        }
+        self.stack.push(object);
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Result<Object, String> {
+        let object = self.stack.pop().ok_or_else(|| String::from("stack underflow"))?;
+        self.last_popped = object.clone();
+        Ok(object)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::Compiler;
+    use crate::lexer::Lexer;
+    use crate::parser::Parser;
+
+    fn run(source: &str) -> Result<Object, String> {
+        let lexer = Lexer::new(source);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program();
+        assert!(parser.errors().is_empty(), "parser errors: {:?}", parser.errors());
+
+        let bytecode = Compiler::new().compile(&program)?;
+        let mut vm = VM::new(bytecode);
+        vm.run()?;
+        Ok(vm.last_popped_stack_element().clone())
+    }
+
+    #[test]
+    fn integer_arithmetic() {
+        assert_eq!(run("5 + 5 * 2 - 10 / 2;").unwrap(), Object::Integer(10));
+    }
+
+    #[test]
+    fn division_by_zero_is_a_vm_error() {
+        assert_eq!(run("10 / 0;").unwrap_err(), "division by zero");
+    }
+
+    #[test]
+    fn integer_overflow_is_a_vm_error() {
+        assert_eq!(
+            run("9223372036854775807 + 1;").unwrap_err(),
+            "integer overflow"
+        );
+    }
+
+    #[test]
+    fn closures_capture_their_defining_environment() {
+        let source = "let adder = fn(x) { fn(y) { x + y } }; adder(5)(10);";
+        assert_eq!(run(source).unwrap(), Object::Integer(15));
+    }
+
+    #[test]
+    fn closures_capture_free_variables_transitively() {
+        let source = "
+            let newAdderOuter = fn(a, b) {
+                fn(c) {
+                    fn(d) { a + b + c + d }
+                }
+            };
+            newAdderOuter(1, 2)(3)(4);
+        ";
+        assert_eq!(run(source).unwrap(), Object::Integer(10));
+    }
+
+    #[test]
+    fn recursive_closures_still_resolve_via_the_global_binding() {
+        let source = "
+            let countdown = fn(x) { if (x == 0) { 0 } else { countdown(x - 1) } };
+            countdown(5);
+        ";
+        assert_eq!(run(source).unwrap(), Object::Integer(0));
+    }
+
+    #[test]
+    fn top_level_return_stops_the_program_with_its_value() {
+        assert_eq!(run("return 5; 10;").unwrap(), Object::Integer(5));
+    }
+}